@@ -0,0 +1,23 @@
+//! Times move generation by counting the positions reachable from the
+//! Glinski start. Run with `cargo bench`.
+
+use std::time::Instant;
+
+use hexagon_core::{Board, Depth, Player, Variant};
+
+const MAX_DEPTH: u8 = 4;
+
+fn main() {
+    let mut board = Board::setup(Variant::Glinski, Depth::GLINSKI).expect("Glinski fits its board");
+
+    for depth in 1..=MAX_DEPTH {
+        let start = Instant::now();
+        let nodes = board.perft(Player::White, depth);
+        let elapsed = start.elapsed();
+
+        println!(
+            "perft {depth}: {nodes:>9} nodes in {elapsed:>9.2?} ({:.0} nodes/s)",
+            nodes as f64 / elapsed.as_secs_f64()
+        );
+    }
+}
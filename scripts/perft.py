@@ -0,0 +1,193 @@
+#!/usr/bin/env python3
+"""Counts Glinski perft from the starting position, independently of the crate.
+
+The board is kept in cube coordinates (q, r, s with q + r + s = 0) rather than
+the crate's rank and file cells, and moves are generated straight from the
+rules, so the counts checked in `src/board/perft.rs` do not rest on the code
+they test. Only the starting FEN is shared.
+
+    python3 scripts/perft.py 3
+
+prints the node count for each depth up to 3. Depth 4 takes a few minutes.
+"""
+
+import sys
+from itertools import permutations
+
+RADIUS = 5
+START = "6/P5p/RP4pr/N1P3p1n/Q2P2p2q/BBB1P1p1bbb/K2P2p2k/N1P3p1n/RP4pr/P5p/6"
+
+ORTHOGONAL = sorted(set(permutations((1, -1, 0))))
+DIAGONAL = sorted(set(permutations((2, -1, -1))) | set(permutations((-2, 1, 1))))
+KNIGHT = sorted(set(permutations((1, 2, -3))) | set(permutations((-1, -2, 3))))
+FORWARD = {"w": (0, 1, -1), "b": (0, -1, 1)}
+
+
+def on_board(cell):
+    return max(abs(x) for x in cell) <= RADIUS
+
+
+def add(a, b):
+    return tuple(x + y for x, y in zip(a, b))
+
+
+def dot(a, b):
+    return sum(x * y for x, y in zip(a, b))
+
+
+def color(piece):
+    return "w" if piece.isupper() else "b"
+
+
+def other(side):
+    return "b" if side == "w" else "w"
+
+
+def parse(placement):
+    """Files a to l, each listed from rank 1 up, digits for empty runs."""
+    board = {}
+    for idx, file in enumerate(placement.split("/")):
+        q = idx - RADIUS
+        rank = 0
+        run = ""
+        for ch in file + "/":
+            if ch.isdigit():
+                run += ch
+                continue
+            rank += int(run or 0)
+            run = ""
+            if ch == "/":
+                break
+            y = 2 * rank + abs(q) - 2 * RADIUS
+            board[(q, (y - q) // 2, (-q - y) // 2)] = ch
+            rank += 1
+    return board
+
+
+START_BOARD = parse(START)
+PAWN_HOMES = {cell for cell, piece in START_BOARD.items() if piece in "Pp"}
+
+
+def pawn_captures(side):
+    """The two orthogonal steps half forward, to either side."""
+    forward = FORWARD[side]
+    return [d for d in ORTHOGONAL if dot(d, forward) == 1 and d != forward]
+
+
+def pseudo_legal(board, side, en_passant):
+    """Moves as (src, dest, promotion, en passant cell left, pawn removed)."""
+    moves = []
+    for src, piece in board.items():
+        if color(piece) != side:
+            continue
+        kind = piece.upper()
+
+        if kind == "P":
+            forward = FORWARD[side]
+
+            def push(dest):
+                if on_board(add(dest, forward)):
+                    moves.append((src, dest, None, None, None))
+                else:
+                    moves.extend((src, dest, promo, None, None) for promo in "QRBN")
+
+            one = add(src, forward)
+            if on_board(one) and one not in board:
+                push(one)
+                two = add(one, forward)
+                if (
+                    src in PAWN_HOMES
+                    and color(START_BOARD[src]) == side
+                    and on_board(two)
+                    and two not in board
+                ):
+                    moves.append((src, two, None, one, None))
+            for step in pawn_captures(side):
+                dest = add(src, step)
+                if not on_board(dest):
+                    continue
+                if dest in board:
+                    if color(board[dest]) != side:
+                        push(dest)
+                elif en_passant and en_passant[0] == dest:
+                    moves.append((src, dest, None, None, en_passant[1]))
+
+        elif kind in "NK":
+            for step in KNIGHT if kind == "N" else ORTHOGONAL + DIAGONAL:
+                dest = add(src, step)
+                if on_board(dest) and (dest not in board or color(board[dest]) != side):
+                    moves.append((src, dest, None, None, None))
+
+        else:
+            steps = {"R": ORTHOGONAL, "B": DIAGONAL, "Q": ORTHOGONAL + DIAGONAL}[kind]
+            for step in steps:
+                dest = add(src, step)
+                while on_board(dest):
+                    if dest in board:
+                        if color(board[dest]) != side:
+                            moves.append((src, dest, None, None, None))
+                        break
+                    moves.append((src, dest, None, None, None))
+                    dest = add(dest, step)
+    return moves
+
+
+def is_attacked(board, cell, by):
+    for step in ORTHOGONAL + DIAGONAL:
+        dest = add(cell, step)
+        adjacent = True
+        while on_board(dest):
+            if dest in board:
+                piece = board[dest]
+                kind = piece.upper()
+                if color(piece) == by and (
+                    kind == "Q"
+                    or (kind == "R" and step in ORTHOGONAL)
+                    or (kind == "B" and step in DIAGONAL)
+                    or (kind == "K" and adjacent)
+                ):
+                    return True
+                break
+            adjacent = False
+            dest = add(dest, step)
+
+    knight = "N" if by == "w" else "n"
+    if any(board.get(add(cell, step)) == knight for step in KNIGHT):
+        return True
+
+    pawn = "P" if by == "w" else "p"
+    return any(
+        board.get(add(cell, tuple(-x for x in step))) == pawn
+        for step in pawn_captures(by)
+    )
+
+
+def legal(board, side, en_passant):
+    """The positions after each legal move, with the en passant target left."""
+    king = "K" if side == "w" else "k"
+    positions = []
+    for src, dest, promo, passed, removed in pseudo_legal(board, side, en_passant):
+        after = dict(board)
+        piece = after.pop(src)
+        if removed:
+            del after[removed]
+        if promo:
+            piece = promo if side == "w" else promo.lower()
+        after[dest] = piece
+
+        king_cell = next(cell for cell, piece in after.items() if piece == king)
+        if not is_attacked(after, king_cell, other(side)):
+            positions.append((after, (passed, dest) if passed else None))
+    return positions
+
+
+def perft(board, side, en_passant, depth):
+    positions = legal(board, side, en_passant)
+    if depth == 1:
+        return len(positions)
+    return sum(perft(after, other(side), ep, depth - 1) for after, ep in positions)
+
+
+if __name__ == "__main__":
+    for depth in range(1, int(sys.argv[1]) + 1):
+        print(depth, perft(START_BOARD, "w", None, depth), flush=True)
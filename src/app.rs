@@ -1,27 +1,126 @@
-use crossterm::event::{self, Event, KeyCode, KeyEvent};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     DefaultTerminal, Frame,
-    layout::{Constraint, Layout},
+    layout::{Constraint, Layout, Rect},
 };
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, TryRecvError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
 use crate::{
-    board::{Board, BoardView},
+    board::{Board, BoardStyle, BoardView},
+    engine::{self, BestMove, SearchLimits},
     pieces::PieceType,
     state::{Panel, State},
-    utils::{depth::Depth, direction::Direction, fill_mode::FillMode},
+    unit::cell::Cell,
+    utils::{
+        clock::TimeControl,
+        controller::Controller,
+        depth::Depth,
+        fill_mode::FillMode,
+        keymap::{Action, Keymap},
+        notation::legal_move_labels,
+        player::Player,
+        prompt::Prompt,
+        scoring::ScoringRule,
+        variant::Variant,
+    },
 };
 
+const DEFAULT_SAVE_PATH: &str = "hexagon.pgn";
+// How often the screen is redrawn while waiting for input, so running
+// clocks visibly count down.
+const TICK_RATE: Duration = Duration::from_millis(100);
+
+/// The engine thinking on a thread of its own, so the screen and clocks keep
+/// going while it does. Dropping it stops the thread.
+struct EngineSearch {
+    // The position searched, which the reply is only good for.
+    position: u64,
+    reply: Receiver<Option<BestMove>>,
+    stop: Arc<AtomicBool>,
+    // Whether the engine already answered that it has no move here.
+    stuck: bool,
+}
+
+impl EngineSearch {
+    fn start(board: &Board, player: Player, scoring: ScoringRule) -> Self {
+        let mut board = board.clone();
+        let position = board.position_hash(player);
+        let (sender, reply) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let stopped = Arc::clone(&stop);
+        thread::spawn(move || {
+            // Nobody is listening any more if the game moved on meanwhile.
+            let _ = sender.send(engine::best_move(
+                &mut board,
+                player,
+                SearchLimits::default(),
+                scoring,
+                &stopped,
+            ));
+        });
+
+        Self {
+            position,
+            reply,
+            stop,
+            stuck: false,
+        }
+    }
+}
+
+impl Drop for EngineSearch {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
 pub struct App {
     terminate: bool,
+    last_tick: Instant,
+    drag_from: Option<Cell>,
+    keymap: Keymap,
+    prompt: Option<Prompt>,
+    search: Option<EngineSearch>,
+    coordinates: bool,
+    style: BoardStyle,
     board: Board,
     state: State,
+    save_path: PathBuf,
+    white: Controller,
+    black: Controller,
 }
 
 impl App {
-    pub fn new(len: f64, padding: f64, color_mode: FillMode, hide_highlights: bool) -> App {
-        Self::from_board(Board::new(len, padding, color_mode, hide_highlights))
+    pub fn new(
+        len: f64,
+        padding: f64,
+        depth: Depth,
+        color_mode: FillMode,
+        hide_highlights: bool,
+        variant: Variant,
+    ) -> App {
+        Self::from_board(
+            Board::with_depth(depth, variant),
+            BoardStyle {
+                len,
+                padding,
+                fill_mode: color_mode,
+                hide_highlights,
+            },
+        )
     }
 
     pub fn preview(
@@ -31,28 +130,223 @@ impl App {
         color_mode: FillMode,
         hide_highlights: bool,
     ) -> App {
-        Self::from_board(Board::preview(
-            len,
-            padding,
-            depth,
-            color_mode,
-            hide_highlights,
-        ))
+        Self::from_board(
+            Board::empty(depth),
+            BoardStyle {
+                len,
+                padding,
+                fill_mode: color_mode,
+                hide_highlights,
+            },
+        )
     }
 
-    fn from_board(board: Board) -> App {
+    pub fn load(
+        path: PathBuf,
+        len: f64,
+        padding: f64,
+        color_mode: FillMode,
+        hide_highlights: bool,
+    ) -> Result<App> {
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let (board, state) = State::from_game_text(&text)
+            .with_context(|| format!("Failed to load {}", path.display()))?;
+
+        Ok(Self {
+            terminate: false,
+            last_tick: Instant::now(),
+            drag_from: None,
+            keymap: Keymap::default(),
+            prompt: None,
+            search: None,
+            coordinates: false,
+            style: BoardStyle {
+                len,
+                padding,
+                fill_mode: color_mode,
+                hide_highlights,
+            },
+            board,
+            state,
+            save_path: path,
+            white: Controller::default(),
+            black: Controller::default(),
+        })
+    }
+
+    fn from_board(board: Board, style: BoardStyle) -> App {
         Self {
             terminate: false,
+            last_tick: Instant::now(),
+            drag_from: None,
+            keymap: Keymap::default(),
+            prompt: None,
+            search: None,
+            coordinates: false,
+            style,
+            state: State::for_board(&board),
             board,
-            state: State::new(),
+            save_path: PathBuf::from(DEFAULT_SAVE_PATH),
+            white: Controller::default(),
+            black: Controller::default(),
         }
     }
 
+    pub fn with_scoring(mut self, scoring: Option<ScoringRule>) -> App {
+        if let Some(scoring) = scoring {
+            self.state.set_scoring(scoring);
+        }
+        self
+    }
+
+    pub fn with_time_control(mut self, control: Option<TimeControl>) -> App {
+        if let Some(control) = control {
+            self.state.start_clock(control);
+        }
+        self
+    }
+
+    pub fn with_controllers(mut self, white: Controller, black: Controller) -> App {
+        self.white = white;
+        self.black = black;
+        self
+    }
+
+    fn engine_to_move(&self) -> bool {
+        self.controller(self.state.player()) == Controller::Ai
+    }
+
+    fn controller(&self, player: Player) -> Controller {
+        match player {
+            Player::White => self.white,
+            Player::Black => self.black,
+        }
+    }
+
+    /// Starts the engine thinking when it is its turn, and plays its move
+    /// once it has one.
+    fn play_engine_move(&mut self) -> bool {
+        let player = self.state.player();
+        if self.controller(player) != Controller::Ai
+            || self.state.is_game_over()
+            || self.state.is_promoting()
+        {
+            self.search = None;
+            return false;
+        }
+
+        let position = self.board.position_hash(player);
+        let Some(search) = self
+            .search
+            .as_mut()
+            .filter(|search| search.position == position)
+        else {
+            self.search = Some(EngineSearch::start(
+                &self.board,
+                player,
+                self.state.scoring(),
+            ));
+            return false;
+        };
+        if search.stuck {
+            return false;
+        }
+
+        let best = match search.reply.try_recv() {
+            Ok(best) => best,
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => {
+                log::error!("The engine stopped without a move");
+                None
+            }
+        };
+        // Searching the same position again would come up empty again, so
+        // wait for the position to change instead.
+        let Some(best) = best else {
+            search.stuck = true;
+            return false;
+        };
+        self.search = None;
+        // Charge the search to the engine before the turn passes.
+        self.tick();
+
+        match self.state.play(&mut self.board, best.src, best.mov) {
+            Ok(()) => true,
+            Err(err) => {
+                log::error!("{err:#}");
+                false
+            }
+        }
+    }
+
+    fn undo(&mut self) {
+        self.search = None;
+        self.state.undo(&mut self.board);
+
+        // Undoing into the engine's turn would only make it replay the same
+        // move, so take back the engine's reply along with the human move.
+        let player = self.state.player();
+        if self.controller(player) == Controller::Ai
+            && self.controller(player.toggle()) == Controller::Human
+        {
+            self.state.undo(&mut self.board);
+        }
+    }
+
+    fn redo(&mut self) {
+        self.search = None;
+        self.state.redo(&mut self.board);
+    }
+
+    fn quit(&mut self) {
+        self.search = None;
+        self.terminate = true;
+    }
+
+    pub fn with_perspective(mut self, perspective: Player, auto_flip: bool) -> App {
+        self.state.set_flipped(perspective == Player::Black);
+        self.state.set_auto_flip(auto_flip);
+        self
+    }
+
+    pub fn with_coordinates(mut self, coordinates: bool) -> App {
+        self.coordinates = coordinates;
+        self
+    }
+
+    pub fn with_keymap(mut self, keymap: Keymap) -> App {
+        self.keymap = keymap;
+        self
+    }
+
+    pub fn with_save_path(mut self, path: PathBuf) -> App {
+        self.save_path = path;
+        self
+    }
+
+    fn save(&self) -> Result<()> {
+        fs::write(&self.save_path, self.state.to_game_text()?)
+            .with_context(|| format!("Failed to write {}", self.save_path.display()))
+    }
+
     pub fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
         while !self.terminate {
             terminal.draw(|frame| self.draw(frame))?;
-            match event::read()? {
-                Event::Key(event) => self.handle_key_event(event),
+            if self.play_engine_move() && !event::poll(Duration::ZERO)? {
+                continue;
+            }
+
+            let event = event::poll(TICK_RATE)?.then(event::read).transpose()?;
+            self.tick();
+
+            match event {
+                Some(Event::Key(event)) => {
+                    self.handle_key_event(event, terminal.get_frame().area())
+                }
+                Some(Event::Mouse(event)) => {
+                    self.handle_mouse_event(event, terminal.get_frame().area())
+                }
                 _ => (),
             }
         }
@@ -60,7 +354,13 @@ impl App {
         Ok(())
     }
 
-    fn handle_key_event(&mut self, key: KeyEvent) {
+    fn tick(&mut self) {
+        let now = Instant::now();
+        self.state.tick(&self.board, now - self.last_tick);
+        self.last_tick = now;
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent, area: Rect) {
         if !key.is_press() {
             return;
         }
@@ -79,48 +379,160 @@ impl App {
                 KeyCode::Char('n') => self
                     .state
                     .select_promotion(&mut self.board, PieceType::Knight),
+                KeyCode::Char('u') => self.undo(),
                 _ => {}
             }
             return;
         }
 
+        if self.prompt.is_some() {
+            self.handle_prompt_key(key);
+            return;
+        }
+
+        let Some(action) = self.keymap.action(key) else {
+            return;
+        };
+
+        match action {
+            Action::Quit => self.quit(),
+            Action::TogglePanel => self.state.toggle_panel(),
+            Action::Save => match self.save() {
+                Ok(()) => log::info!("Saved game to {}", self.save_path.display()),
+                Err(err) => log::error!("{err:#}"),
+            },
+            // Directions are as seen on screen, so they turn with the board.
+            Action::Move(direction) => {
+                let direction = if self.state.is_flipped() {
+                    direction.reverse()
+                } else {
+                    direction
+                };
+                self.state.move_current(&self.board, direction)
+            }
+            Action::Flip => self.state.set_flipped(!self.state.is_flipped()),
+            Action::Coordinates => self.coordinates = !self.coordinates,
+            Action::Select if !self.engine_to_move() => {
+                self.state.toggle_help_or_move(&mut self.board)
+            }
+            Action::Select => {}
+            Action::Undo => self.undo(),
+            Action::Redo => self.redo(),
+            Action::ScrollUp => self.scroll_history_up(area),
+            Action::ScrollDown => self.state.scroll_history_down(),
+            Action::Command => self.prompt = Some(Prompt::default()),
+        }
+    }
+
+    fn handle_prompt_key(&mut self, key: KeyEvent) {
+        let engine_to_move = self.engine_to_move();
+        let Some(prompt) = self.prompt.as_mut() else {
+            return;
+        };
+
         match key.code {
-            KeyCode::Char('q') => self.terminate = true,
-            KeyCode::Char('p') => self.state.toggle_panel(),
-            KeyCode::Left => self.state.move_current(&mut self.board, Direction::Clock10),
-            KeyCode::Right => self.state.move_current(&mut self.board, Direction::Clock2),
-            KeyCode::Up => self.state.move_current(&mut self.board, Direction::Clock12),
-            KeyCode::Down => self.state.move_current(&mut self.board, Direction::Clock6),
-            KeyCode::Enter => self.state.toggle_help_or_move(&mut self.board),
+            KeyCode::Esc => self.prompt = None,
+            KeyCode::Enter => {
+                let entered = if engine_to_move {
+                    Err(anyhow::anyhow!("It is the engine's turn"))
+                } else {
+                    self.state.enter_move(&mut self.board, prompt.input())
+                };
+
+                match entered {
+                    Ok(()) => self.prompt = None,
+                    Err(err) => prompt.set_error(format!("{err:#}")),
+                }
+            }
+            KeyCode::Tab => {
+                let player = self.state.player();
+                prompt.complete(|| legal_move_labels(&mut self.board, player));
+            }
+            KeyCode::Backspace if prompt.input().is_empty() => self.prompt = None,
+            KeyCode::Backspace => prompt.pop(),
+            KeyCode::Char(c) => prompt.push(c),
             _ => {}
         }
     }
 
-    fn draw(&self, frame: &mut Frame) {
-        match self.state.panel() {
-            Panel::Hidden => {
-                let board_view = BoardView {
-                    board: &self.board,
-                    div: 2.,
-                };
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, area: Rect) {
+        let (board_view, board_area, _) = self.layout(area);
+        let cell = board_view.cell_at(board_area, mouse.column, mouse.row);
+
+        // The board only answers clicks while the prompt is closed and the
+        // engine is not to move; the history still scrolls.
+        let prompting = self.prompt.is_some() || self.engine_to_move();
 
-                frame.render_widget(&board_view, frame.area());
+        match (mouse.kind, cell) {
+            (MouseEventKind::Down(MouseButton::Left), Some(cell)) if !prompting => {
+                self.state.click(&mut self.board, cell);
+                self.drag_from = Some(cell);
             }
+            (MouseEventKind::Up(MouseButton::Left), cell) => {
+                if let (Some(src), Some(cell)) = (self.drag_from.take(), cell)
+                    && !prompting
+                {
+                    self.state.drop(&mut self.board, src, cell);
+                }
+            }
+            (MouseEventKind::ScrollUp, _) => self.scroll_history_up(area),
+            (MouseEventKind::ScrollDown, _) => self.state.scroll_history_down(),
+            _ => {}
+        }
+    }
+
+    fn scroll_history_up(&mut self, area: Rect) {
+        if let (_, _, Some(state_area)) = self.layout(area) {
+            self.state
+                .scroll_history_up(State::history_rows(state_area));
+        }
+    }
+
+    /// The board view and where it and the side panel go in `area`, once
+    /// the bottom line is taken for the prompt if it is open.
+    fn layout(&self, area: Rect) -> (BoardView<'_>, Rect, Option<Rect>) {
+        let area = match self.prompt {
+            Some(_) => Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(area)[0],
+            None => area,
+        };
+
+        match self.state.panel() {
+            Panel::Hidden => (self.board_view(2.), area, None),
             Panel::Visible { width_percentage } => {
                 let [board_area, state_area] = Layout::horizontal([
                     Constraint::Percentage(100 - *width_percentage),
                     Constraint::Percentage(*width_percentage),
                 ])
-                .areas(frame.area());
+                .areas(area);
 
-                let board_view = BoardView {
-                    board: &self.board,
-                    div: 2.75,
-                };
-
-                frame.render_widget(&board_view, board_area);
-                frame.render_widget(&self.state, state_area);
+                (self.board_view(2.75), board_area, Some(state_area))
             }
         }
     }
+
+    fn board_view(&self, div: f64) -> BoardView<'_> {
+        BoardView {
+            board: &self.board,
+            style: self.style,
+            flipped: self.state.is_flipped(),
+            current: self.state.current(),
+            moves: self.state.selected_moves(),
+            div,
+            coordinates: self.coordinates,
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let (board_view, board_area, state_area) = self.layout(frame.area());
+
+        frame.render_widget(&board_view, board_area);
+        if let Some(state_area) = state_area {
+            frame.render_widget(&self.state, state_area);
+        }
+        if let Some(prompt) = &self.prompt {
+            let [_, prompt_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+            frame.render_widget(prompt, prompt_area);
+        }
+    }
 }
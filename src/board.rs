@@ -1,86 +1,89 @@
-use std::{
-    borrow::Borrow,
-    ops::{Index, IndexMut},
-    ptr::NonNull,
-};
+use std::ops::{Index, IndexMut};
+
+use anyhow::{Result, bail};
 
 use crate::{
-    hexagon::Hexagon,
-    pieces::{
-        Piece, PieceType,
-        bishop::{BLACK_BISHOP_STARTING_CELLS, Bishop, WHITE_BISHOP_STARTING_CELLS},
-        king::{BLACK_KING_STARTING_LOCATION, King, WHITE_KING_STARTING_LOCATION},
-        knight::{BLACK_KNIGHT_STARTING_CELLS, Knight, WHITE_KNIGHT_STARTING_CELLS},
-        pawn::{BLACK_PAWN_STARTING_CELLS, Pawn, WHITE_PAWN_STARTING_CELLS},
-        queen::{BLACK_QUEEN_STARTING_LOCATION, Queen, WHITE_QUEEN_STARTING_LOCATION},
-        rook::{BLACK_ROOK_STARTING_CELLS, Rook, WHITE_ROOK_STARTING_CELLS},
-    },
-    state::State,
+    pieces::PieceType,
     unit::cell::Cell,
     utils::{
-        consts::{TERM_SCALE_FACTOR, TONE_CANVAS_BG, TONE_HEX_BG1, TONE_HEX_BG2, TONE_HEX_BG3},
-        delta::Delta,
+        castling::CastlingRights,
         depth::Depth,
         direction::Direction,
         entry::Entry,
-        file::File,
-        fill_mode::FillMode,
-        mark::Mark,
-        mode::{HighlightMode, Status},
         moves::{GeneralMoveType, Move, MoveType, PawnMoveType},
-        range::Range,
-        rank::Rank,
+        player::Player,
+        variant::Variant,
     },
 };
 
-use ratatui::{
-    style::{Color, Style, Stylize},
-    text::Line,
-    widgets::{
-        Block, Borders, Widget,
-        canvas::{Canvas, Shape},
-    },
-};
-use strum::IntoEnumIterator;
-
+mod attacks;
+mod fen;
+#[cfg(feature = "tui")]
+mod hash;
+mod material;
+mod movegen;
+mod perft;
+#[cfg(feature = "tui")]
+mod view;
+
+#[cfg(feature = "tui")]
+pub(crate) use fen::{STARTING_FEN, cell_label};
+#[cfg(feature = "tui")]
+pub(crate) use view::{BoardStyle, BoardView};
+
+use self::attacks::{AttackTables, pawn_forward};
+
+/// A pawn that just advanced more than one cell. It may be taken en passant
+/// on any cell it passed over, from `first_passed` up to the pawn itself, so a
+/// long Shafran run is no safer than a double step.
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct EnPassant {
     pub(crate) captured_pawn: Cell,
-    pub(crate) capture_move_to: Cell,
-    pub(crate) pawn_color: Color,
+    pub(crate) first_passed: Cell,
+    pub(crate) pawn_color: Player,
 }
 
-struct MoveUndo {
-    src: Cell,
-    dest: Cell,
-    move_type: MoveType,
-    moved_color: Color,
-    captured_piece: Option<Box<dyn Piece>>,
-    en_passant_captured: Option<(Cell, Box<dyn Piece>)>,
+impl EnPassant {
+    fn passed(self) -> impl Iterator<Item = Cell> {
+        let forward = pawn_forward(self.pawn_color);
+        std::iter::successors(Some(self.first_passed), move |cell| cell.next(forward))
+            .take_while(move |cell| *cell != self.captured_pawn)
+    }
 }
 
-pub(crate) struct Board {
+/// What [`Board::make_move`] changed, for [`Board::unmake_move`] to put back.
+#[derive(Clone, Copy, Debug)]
+#[must_use]
+pub struct UndoToken {
+    pub(crate) src: Cell,
+    pub(crate) mov: Move,
+    pub(crate) captured: Option<PieceType>,
+    pub(crate) en_passant: Option<EnPassant>,
+    pub(crate) castling: CastlingRights,
+    pub(crate) halfmove_clock: u32,
+}
+
+#[derive(Clone)]
+pub struct Board {
     inner: Vec<Entry>,
     depth: Depth,
+    tables: &'static AttackTables,
     en_passant: Option<EnPassant>,
-
-    hide_highlights: bool,
+    castling: CastlingRights,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    variant: Variant,
 }
 
-#[macro_export]
+#[cfg(test)]
 macro_rules! board_set {
     ($(
         ($color: expr, $path: ident, $piece: ident $(,)?)
             on
         [$rank: expr, $file: expr $(,)?]),* $(,)?
     ) => {{
-        let mut board = $crate::board::Board::empty(
-            0.,
-            0.,
-            $crate::utils::depth::Depth::new(6).unwrap(),
-            $crate::utils::fill_mode::FillMode::Wireframe,
-            false,
-        );
+        let mut board =
+            $crate::board::Board::empty($crate::utils::depth::Depth::new(6).unwrap());
 
         $(
             let cell = $crate::unit::cell::Cell::try_new($rank, $file).unwrap();
@@ -94,6 +97,9 @@ macro_rules! board_set {
         board
     }};
 }
+// Reached by path from the engine, state and notation tests.
+#[cfg(all(test, feature = "tui"))]
+pub(crate) use board_set;
 
 impl Index<Cell> for Board {
     type Output = Entry;
@@ -109,284 +115,301 @@ impl IndexMut<Cell> for Board {
 }
 
 impl Board {
-    pub(crate) fn empty(
-        len: f64,
-        padding: f64,
-        depth: Depth,
-        fill_mode: FillMode,
-        hide_highlights: bool,
-    ) -> Self {
-        let colors = [TONE_HEX_BG1, TONE_HEX_BG2, TONE_HEX_BG3];
-        let num_files = depth.file_range().remaining() as usize;
-
-        let inner = depth
-            .file_range()
-            .enumerate()
-            .flat_map(|(idx, file)| {
-                depth
-                    .rank_range(file)
-                    .zip(
-                        colors
-                            .into_iter()
-                            .cycle()
-                            .skip(idx.min((num_files - 1) - idx)),
-                    )
-                    .map(move |(rank, color)| {
-                        let hex = Hexagon::new(
-                            rank,
-                            file,
-                            len,
-                            padding,
-                            color,
-                            fill_mode,
-                            HighlightMode::new(
-                                file == File::default() && rank == Rank::default(),
-                                Status::default(),
-                            ),
-                        );
-
-                        Entry::new(hex, None, hide_highlights)
-                    })
-            })
-            .collect();
+    pub(crate) fn empty(depth: Depth) -> Self {
+        let tables = AttackTables::for_depth(depth);
 
         Self {
-            inner,
+            inner: (0..tables.len()).map(|_| Entry::default()).collect(),
             depth,
+            tables,
             en_passant: None,
-            hide_highlights,
+            castling: CastlingRights::default(),
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            variant: Variant::default(),
         }
     }
 
-    pub(crate) fn new(len: f64, padding: f64, fill_mode: FillMode, hide_highlights: bool) -> Self {
-        let mut board = Self::empty(len, padding, Depth::default(), fill_mode, hide_highlights);
-
-        WHITE_BISHOP_STARTING_CELLS
-            .into_iter()
-            .for_each(|cell| board[cell].set_occupant(Bishop::new(Color::White)));
-
-        BLACK_BISHOP_STARTING_CELLS
-            .into_iter()
-            .for_each(|cell| board[cell].set_occupant(Bishop::new(Color::Black)));
-
-        board[WHITE_KING_STARTING_LOCATION].set_occupant(King::new(Color::White));
-
-        board[BLACK_KING_STARTING_LOCATION].set_occupant(King::new(Color::Black));
-
-        board[WHITE_QUEEN_STARTING_LOCATION].set_occupant(Queen::new(Color::White));
-
-        board[BLACK_QUEEN_STARTING_LOCATION].set_occupant(Queen::new(Color::Black));
-
-        WHITE_ROOK_STARTING_CELLS
-            .into_iter()
-            .for_each(|cell| board[cell].set_occupant(Rook::new(Color::White)));
-
-        BLACK_ROOK_STARTING_CELLS
-            .into_iter()
-            .for_each(|cell| board[cell].set_occupant(Rook::new(Color::Black)));
-
-        WHITE_KNIGHT_STARTING_CELLS
-            .into_iter()
-            .for_each(|cell| board[cell].set_occupant(Knight::new(Color::White)));
+    #[cfg(test)]
+    pub(crate) fn new() -> Self {
+        Self::with_variant(Variant::default())
+    }
 
-        BLACK_KNIGHT_STARTING_CELLS
-            .into_iter()
-            .for_each(|cell| board[cell].set_occupant(Knight::new(Color::Black)));
+    #[cfg(test)]
+    pub(crate) fn with_variant(variant: Variant) -> Self {
+        Self::with_depth(Depth::default(), variant)
+    }
 
-        WHITE_PAWN_STARTING_CELLS
-            .into_iter()
-            .for_each(|cell| board[cell].set_occupant(Pawn::new(Color::White)));
+    /// A game of `variant` set up on a board of `depth`, which the variant
+    /// must be [playable on](Variant::playable_on).
+    pub(crate) fn with_depth(depth: Depth, variant: Variant) -> Self {
+        let mut board = Self::empty(depth);
+        board.variant = variant;
+        board.castling = variant.castling_rights();
 
-        BLACK_PAWN_STARTING_CELLS
-            .into_iter()
-            .for_each(|cell| board[cell].set_occupant(Pawn::new(Color::Black)));
+        for color in [Player::White, Player::Black] {
+            for (piece_type, cell) in variant.setup(color, depth) {
+                board[cell].replace_occupant((piece_type, color));
+            }
+        }
 
         board
     }
 
-    pub(crate) fn preview(
-        len: f64,
-        padding: f64,
-        depth: Depth,
-        fill_mode: FillMode,
-        hide_highlights: bool,
-    ) -> Self {
-        Self::empty(len, padding, depth, fill_mode, hide_highlights)
+    /// A game of `variant` set up on a board of `depth`.
+    pub fn setup(variant: Variant, depth: Depth) -> Result<Self> {
+        if !variant.playable_on(depth) {
+            bail!(
+                "the {} variant has no starting position on a board of depth {}",
+                variant.label(),
+                depth.radius() + 1
+            );
+        }
+
+        Ok(Self::with_depth(depth, variant))
     }
 
-    pub(crate) fn legal_moves(&mut self, cell: Cell) -> Vec<Move> {
-        let (color, moves) = {
-            let Some(occupant) = self[cell].occupant() else {
-                return vec![];
-            };
-            (occupant.color(), occupant.valid_moves(self, cell))
+    /// The moves of the piece on `cell` that do not leave its own king in
+    /// check, each tried out with [`Board::make_move`] and taken back.
+    pub fn legal_moves(&mut self, cell: Cell) -> Vec<Move> {
+        let Some((_, color)) = self[cell].square() else {
+            return vec![];
         };
 
-        moves
+        self.pseudo_legal_moves(cell)
             .into_iter()
             .filter(|mov| {
-                if self[mov.move_to]
-                    .occupant()
-                    .is_some_and(|piece| piece.ty() == PieceType::King)
-                {
+                if matches!(self[mov.move_to].square(), Some((PieceType::King, _))) {
                     return false;
                 }
 
-                let Some(undo) = self.apply_for_legality(cell, *mov) else {
+                if let MoveType::Rest(GeneralMoveType::Castle { rook_to, .. }) = mov.move_type
+                    && self.castles_through_check(color, cell, rook_to)
+                {
                     return false;
-                };
-                let legal = !self.is_in_check(color);
-                self.undo_for_legality(undo);
-                legal
+                }
+
+                self.with_move_applied(cell, *mov, |board| !board.is_in_check(color))
             })
             .collect()
     }
 
-    pub(crate) fn is_in_check(&self, color: Color) -> bool {
-        let Some(king_cell) = self.inner.iter().find_map(|entry| {
-            let piece = entry.occupant()?;
-            (piece.color() == color && piece.ty() == PieceType::King).then(|| entry.hex().cell())
-        }) else {
-            return false;
-        };
-
-        self.inner.iter().any(|entry| {
-            let Some(piece) = entry.occupant() else {
-                return false;
-            };
+    // A king may not castle out of check or across a cell the opponent
+    // attacks; landing in check is caught like any other move.
+    fn castles_through_check(&mut self, color: Player, king: Cell, passed: Cell) -> bool {
+        if self.is_in_check(color) {
+            return true;
+        }
 
-            piece.color() != color
-                && piece
-                    .valid_moves(self, entry.hex().cell())
-                    .iter()
-                    .any(|mov| mov.move_to == king_cell)
-        })
+        let step = Move::new(passed, MoveType::Rest(GeneralMoveType::NonCapture));
+        self.with_move_applied(king, step, |board| board.is_in_check(color))
     }
 
-    pub(crate) fn has_legal_move(&mut self, color: Color) -> bool {
-        let cells = self
-            .inner
+    pub fn occupied_cells(&self, color: Player) -> Vec<Cell> {
+        self.inner
             .iter()
-            .filter_map(|entry| {
-                let piece = entry.occupant()?;
-                (piece.color() == color).then(|| entry.hex().cell())
+            .enumerate()
+            .filter_map(|(idx, entry)| {
+                let (_, player) = entry.square()?;
+                (player == color).then(|| self.tables.cell(idx))
             })
-            .collect::<Vec<_>>();
+            .collect()
+    }
 
-        cells
+    pub fn has_legal_move(&mut self, color: Player) -> bool {
+        self.occupied_cells(color)
             .into_iter()
             .any(|cell| !self.legal_moves(cell).is_empty())
     }
 
-    fn apply_for_legality(&mut self, src: Cell, mov: Move) -> Option<MoveUndo> {
-        let moved_piece = self[src].remove_occupant()?;
-        let moved_color = moved_piece.color();
-        let captured_piece = self[mov.move_to].replace_occupant(moved_piece);
+    /// Plays `mov` from `src` and updates the en passant cell, castling
+    /// rights and move counters to match. The move is not checked for
+    /// legality.
+    ///
+    /// # Panics
+    ///
+    /// If there is no piece on `src`.
+    pub fn make_move(&mut self, src: Cell, mov: Move) -> UndoToken {
+        let dest = mov.move_to;
+        let (piece_type, color) = self[src]
+            .square()
+            .unwrap_or_else(|| panic!("there is no piece on {} to move", src.label()));
+        let captured = match mov.move_type {
+            MoveType::Pawn(PawnMoveType::EnPassant { .. }) => Some(PieceType::Pawn),
+            _ => self[dest].square().map(|(piece_type, _)| piece_type),
+        };
 
-        let en_passant_captured = match mov.move_type {
-            MoveType::Pawn(PawnMoveType::EnPassant { remove_piece_on }) => self[remove_piece_on]
-                .remove_occupant()
-                .map(|piece| (remove_piece_on, piece)),
-            _ => None,
+        let undo = UndoToken {
+            src,
+            mov,
+            captured,
+            en_passant: self.en_passant,
+            castling: self.castling,
+            halfmove_clock: self.halfmove_clock,
         };
 
-        if mov.move_type.is_promotion() {
-            self[mov.move_to].remove_occupant();
-            self[mov.move_to].set_occupant(Queen::new(moved_color));
+        self.en_passant = self.en_passant_after(src, dest);
+        self.castling = self.castling_after(src, dest);
+        self.advance_clocks(
+            color,
+            piece_type == PieceType::Pawn || mov.move_type.is_capture(),
+        );
+
+        self.move_occupant(src, dest);
+        match mov.move_type {
+            MoveType::Pawn(PawnMoveType::EnPassant { remove_piece_on }) => {
+                self[remove_piece_on].remove_occupant();
+            }
+            MoveType::Rest(GeneralMoveType::Castle {
+                rook_from, rook_to, ..
+            }) => {
+                self.move_occupant(rook_from, rook_to);
+            }
+            _ => {}
+        }
+        if let Some(promote_to) = mov.move_type.promotion() {
+            self[dest].replace_occupant((promote_to, color));
         }
 
-        Some(MoveUndo {
-            src,
-            dest: mov.move_to,
-            move_type: mov.move_type,
-            moved_color,
-            captured_piece,
-            en_passant_captured,
-        })
+        undo
     }
 
-    fn undo_for_legality(&mut self, undo: MoveUndo) {
-        let moved_piece = self[undo.dest].remove_occupant();
+    /// Takes back the move `undo` was returned for, which must be the last
+    /// one made on this board.
+    pub fn unmake_move(&mut self, undo: UndoToken) {
+        let UndoToken { src, mov, .. } = undo;
+        let dest = mov.move_to;
+
+        if let MoveType::Rest(GeneralMoveType::Castle {
+            rook_from, rook_to, ..
+        }) = mov.move_type
+        {
+            self.move_occupant(rook_to, rook_from);
+        }
 
-        if undo.move_type.is_promotion() {
-            self[undo.src].set_occupant(Pawn::new(undo.moved_color));
-        } else if let Some(moved_piece) = moved_piece {
-            self[undo.src].replace_occupant(moved_piece);
+        let (piece_type, color) = self[dest]
+            .remove_occupant()
+            .expect("the moved piece is on its destination");
+        if mov.move_type.is_promotion() {
+            self[src].replace_occupant((PieceType::Pawn, color));
         } else {
-            unreachable!("simulated move destination is empty");
+            self[src].replace_occupant((piece_type, color));
         }
 
-        if let Some(captured_piece) = undo.captured_piece {
-            self[undo.dest].replace_occupant(captured_piece);
+        match (mov.move_type, undo.captured) {
+            (MoveType::Pawn(PawnMoveType::EnPassant { remove_piece_on }), _) => {
+                self[remove_piece_on].replace_occupant((PieceType::Pawn, color.toggle()));
+            }
+            (_, Some(captured)) => {
+                self[dest].replace_occupant((captured, color.toggle()));
+            }
+            (_, None) => {}
         }
-        if let Some((cell, captured_piece)) = undo.en_passant_captured {
-            self[cell].replace_occupant(captured_piece);
+
+        self.en_passant = undo.en_passant;
+        self.castling = undo.castling;
+        self.halfmove_clock = undo.halfmove_clock;
+        if color == Player::Black {
+            self.fullmove_number -= 1;
         }
     }
 
-    pub(crate) fn show_valid_moves(&mut self, cell: Cell) {
-        self.legal_moves(cell).into_iter().for_each(|mov| {
-            match mov.move_type {
-                MoveType::Rest(GeneralMoveType::Capture)
-                | MoveType::Pawn(PawnMoveType::CapturePromotion)
-                | MoveType::Pawn(PawnMoveType::NormalCapture) => {
-                    self[mov.move_to].hex_mut().set_status(Status::Capturable);
-                }
-                MoveType::Rest(GeneralMoveType::NonCapture)
-                | MoveType::Pawn(PawnMoveType::NonCapturePromotion)
-                | MoveType::Pawn(PawnMoveType::NonCapture) => {
-                    self[mov.move_to].hex_mut().set_status(Status::Movable);
-                }
-                MoveType::Pawn(PawnMoveType::EnPassant { remove_piece_on }) => {
-                    self[mov.move_to].hex_mut().set_status(Status::Movable);
-                    self[remove_piece_on]
-                        .hex_mut()
-                        .set_status(Status::Capturable);
-                }
-            };
-        });
+    /// Calls `f` with `mov` made on the board, then takes it back.
+    pub(crate) fn with_move_applied<T>(
+        &mut self,
+        src: Cell,
+        mov: Move,
+        f: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let undo = self.make_move(src, mov);
+        let result = f(self);
+        self.unmake_move(undo);
+        result
     }
 
-    pub(crate) fn hide_valid_moves(&mut self, cell: Cell) {
-        self.legal_moves(cell).into_iter().for_each(|mov| {
-            match mov.move_type {
-                MoveType::Rest(GeneralMoveType::Capture)
-                | MoveType::Pawn(PawnMoveType::CapturePromotion)
-                | MoveType::Pawn(PawnMoveType::NormalCapture) => {
-                    self[mov.move_to].hex_mut().set_status(Status::None);
-                }
-                MoveType::Rest(GeneralMoveType::NonCapture)
-                | MoveType::Pawn(PawnMoveType::NonCapturePromotion)
-                | MoveType::Pawn(PawnMoveType::NonCapture) => {
-                    self[mov.move_to].hex_mut().set_status(Status::None);
-                }
-                MoveType::Pawn(PawnMoveType::EnPassant { remove_piece_on }) => {
-                    self[mov.move_to].hex_mut().set_status(Status::None);
-                    self[remove_piece_on].hex_mut().set_status(Status::None);
-                }
-            };
-        });
+    pub(crate) fn move_occupant(&mut self, src: Cell, dest: Cell) -> Option<(PieceType, Player)> {
+        let occupant = self[src].remove_occupant()?;
+        self[dest].replace_occupant(occupant)
     }
 
-    pub(crate) fn move_occupant(&mut self, src: Cell, dest: Cell) -> Option<Box<dyn Piece>> {
-        let Some(src_occupant) = self[src].remove_occupant() else {
-            return None;
+    pub(crate) fn set_en_passant(&mut self, en_passant: EnPassant) {
+        self.en_passant = Some(en_passant);
+    }
+
+    pub(crate) fn advance_clocks(&mut self, mover: Player, irreversible: bool) {
+        self.halfmove_clock = if irreversible {
+            0
+        } else {
+            self.halfmove_clock + 1
         };
 
-        self[dest].replace_occupant(src_occupant)
+        if mover == Player::Black {
+            self.fullmove_number += 1;
+        }
     }
 
-    pub(crate) fn clear_en_passant(&mut self) {
-        self.en_passant = None;
+    pub fn depth(&self) -> Depth {
+        self.depth
     }
 
-    pub(crate) fn set_en_passant(&mut self, en_passant: EnPassant) {
-        self.en_passant = Some(en_passant);
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant;
+    }
+
+    #[cfg(test)]
+    pub(crate) fn castling(&self) -> CastlingRights {
+        self.castling
+    }
+
+    /// The castling rights left once a piece moves from `src` to `dest`:
+    /// moving the king gives up both wings, moving or losing a rook its own.
+    pub(crate) fn castling_after(&self, src: Cell, dest: Cell) -> CastlingRights {
+        let mut castling = self.castling;
+        for color in [Player::White, Player::Black] {
+            for candidate in self.variant.castlings(color) {
+                if [candidate.king, candidate.rook].contains(&src) || candidate.rook == dest {
+                    castling.revoke(color, candidate.wing);
+                }
+            }
+        }
+
+        castling
+    }
+
+    pub fn halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
     }
 
-    pub(crate) fn en_passant_capture(&self, attacker_color: Color, move_to: Cell) -> Option<Cell> {
+    pub(crate) fn en_passant_after(&self, src: Cell, dest: Cell) -> Option<EnPassant> {
+        let (PieceType::Pawn, pawn_color) = self[src].square()? else {
+            return None;
+        };
+
+        let forward = pawn_forward(pawn_color);
+
+        let first_passed = src.next(forward)?;
+        std::iter::successors(first_passed.next(forward), |cell| cell.next(forward))
+            .any(|cell| cell == dest)
+            .then_some(EnPassant {
+                captured_pawn: dest,
+                first_passed,
+                pawn_color,
+            })
+    }
+
+    pub(crate) fn en_passant_capture(&self, attacker_color: Player, move_to: Cell) -> Option<Cell> {
         self.en_passant.and_then(|en_passant| {
-            if en_passant.pawn_color != attacker_color && en_passant.capture_move_to == move_to {
+            if en_passant.pawn_color != attacker_color
+                && en_passant.passed().any(|cell| cell == move_to)
+            {
                 Some(en_passant.captured_pawn)
             } else {
                 None
@@ -396,11 +419,8 @@ impl Board {
 }
 
 impl Board {
-    //TODO: OPTIMISE
     fn board_index(&self, cell: Cell) -> usize {
-        (Range::new(self.depth.first_file(), cell.file).fold(0, |accum, file| {
-            accum + self.depth.rank_range(file).remaining()
-        }) + (cell.rank - self.depth.first_rank())) as usize
+        self.tables.index(cell.rank, cell.file)
     }
 
     pub(crate) fn get(&self, cell: Cell) -> Option<&Entry> {
@@ -412,27 +432,9 @@ impl Board {
         self.inner.get_mut(idx)
     }
 
-    // pub(crate) fn set_current(&mut self, cell: Cell) {
-    //     let current_cell = self.current;
-    //
-    //     self[current_cell].hex_mut().set_current(false);
-    //     self[cell].hex_mut().set_current(true);
-    //
-    //     self.current = cell;
-    // }
-
-    pub(crate) fn next(&self, cell: Cell, direction: Direction) -> Option<Cell> {
+    pub fn next(&self, cell: Cell, direction: Direction) -> Option<Cell> {
         cell.next(direction)
-            .map(|cell| {
-                if self.depth.file_range().contains(cell.file)
-                    && self.depth.rank_range(cell.file).contains(cell.rank)
-                {
-                    Some(cell)
-                } else {
-                    None
-                }
-            })
-            .flatten()
+            .filter(|cell| self.depth.contains(cell.rank, cell.file))
     }
 
     // pub(crate) fn move_current(&mut self, direction: Direction) {
@@ -456,103 +458,45 @@ impl Board {
     // }
 }
 
-pub(crate) struct BoardView<'a> {
-    pub(crate) board: &'a Board,
-    pub(crate) div: f64,
-}
-
-impl<'a> Widget for &'a BoardView<'a> {
-    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer)
-    where
-        Self: Sized,
-    {
-        // let mut width = area.width;
-        // let mut height = (width as f64 / TERM_SCALE_FACTOR).floor() as u16;
-        // log::info!("{width}, {height}");
-        log::info!("{}, {}", area.width, area.height);
-
-        // if height == 0 || height > area.height {
-        //     height = area.height;
-        //     width = (height as f64 * TERM_SCALE_FACTOR).floor() as u16;
-        // }
-
-        // let render_area = ratatui::prelude::Rect {
-        //     x: area.x + area.width.saturating_sub(width) / 2,
-        //     y: area.y + area.height.saturating_sub(height) / 2,
-        //     width,
-        //     height,
-        // };
-
-        // let y_dim = render_area.height as f64;
-        // let x_dim = y_dim * TERM_SCALE_FACTOR;
-
-        let scale_factor = area.width as f64 / area.height as f64;
-        log::info!("Scale Factor: {scale_factor}");
-        let y_dim = area.height as f64;
-        let x_dim = y_dim as f64 * TERM_SCALE_FACTOR;
-
-        // let x_dim = area.width as f64;
-        // let y_dim = x_dim as f64 / TERM_SCALE_FACTOR;
-
-        Canvas::default()
-            .x_bounds([-x_dim / self.div, x_dim / self.div])
-            .y_bounds([-y_dim / 2., y_dim / 2.])
-            .block(Block::default().borders(Borders::ALL))
-            .marker(ratatui::symbols::Marker::Braille)
-            .background_color(TONE_CANVAS_BG)
-            .paint(|ctx| {
-                self.board.inner.iter().for_each(|entry| {
-                    ctx.draw(entry);
-
-                    // let hex = entry.hex();
-                    //
-                    // let base = HexagonBase::from(*hex);
-                    // ctx.draw(&base);
-                    //
-                    // if !self.hide_highlights {
-                    //     let base = HexagonHighlights::from(*hex);
-                    //     ctx.draw(&base);
-                    // }
-                    //
-                    // let Delta { x, y } = hex.center();
-
-                    // if let Some(piece) = entry.occupant() {
-                    // let style = match piece.color() {
-                    //     Color::Black => Style::new().white().on_black().bold(),
-                    //     Color::White => Style::new().black().on_white().bold(),
-                    //     _ => unreachable!(),
-                    // };
-                    // ctx.print(x, y, Line::styled(piece.mark(), style));
-
-                    // ctx.draw(piece.mark());
-                    // };
-                });
-            })
-            .render(area, buf)
-    }
-}
-
 #[cfg(test)]
 mod tests {
-    use ratatui::style::Color;
-
     use crate::{
-        pieces::{Piece, king::King, pawn::Pawn, rook::Rook},
+        pieces::{PieceType, king::King, pawn::Pawn, queen::Queen, rook::Rook},
         unit::cell::Cell,
         utils::{
+            castling::Wing,
             depth::Depth,
+            direction::Direction,
             file::File,
-            fill_mode::FillMode,
-            moves::{MoveType, PawnMoveType},
+            moves::{GeneralMoveType, Move, MoveType, PawnMoveType},
+            player::Player,
             rank::Rank,
+            variant::Variant,
         },
     };
 
     use super::{Board, EnPassant};
 
+    fn castling_board(fen: &str) -> Board {
+        let (mut board, _) = Board::from_fen(fen).unwrap();
+        board.set_variant(Variant::Shafran);
+        board
+    }
+
+    fn castles(board: &mut Board, king: Cell) -> Vec<Wing> {
+        board
+            .legal_moves(king)
+            .into_iter()
+            .filter_map(|mov| match mov.move_type {
+                MoveType::Rest(GeneralMoveType::Castle { wing, .. }) => Some(wing),
+                _ => None,
+            })
+            .collect()
+    }
+
     #[test]
     fn new_creates_full_depth_board_with_starting_pieces() {
-        let board = Board::new(0., 0., FillMode::Wireframe, false);
+        let board = Board::new();
 
         assert_eq!(board.inner.len(), 91);
         assert_eq!(
@@ -566,28 +510,66 @@ mod tests {
     }
 
     #[test]
-    fn preview_uses_requested_depth_without_pieces() {
-        let board = Board::preview(0., 0., Depth::new(1).unwrap(), FillMode::Wireframe, false);
+    fn empty_boards_have_the_requested_depth() {
+        let board = Board::empty(Depth::new(1).unwrap());
 
         assert_eq!(board.inner.len(), 1);
         assert!(board.inner.iter().all(|entry| entry.occupant().is_none()));
     }
 
+    #[test]
+    fn bigger_boards_add_a_ring_per_depth() {
+        for (raw, cells, last_file) in [(7, 127, File::FileN), (8, 169, File::FileP)] {
+            let depth = Depth::new(raw).unwrap();
+            let board = Board::empty(depth);
+
+            assert_eq!(board.inner.len(), cells);
+            assert_eq!(depth.file_range().hi(), last_file);
+            assert_eq!(
+                depth.rank_range(File::FileA).hi(),
+                Rank::ALL[raw as usize - 1]
+            );
+            assert_eq!(
+                depth.rank_range(depth.centre().file).hi(),
+                Rank::ALL[2 * raw as usize - 2]
+            );
+        }
+
+        assert!(Depth::new(Depth::MAX).is_ok());
+        assert!(Depth::new(Depth::MAX + 1).is_err());
+    }
+
+    #[test]
+    fn sliders_cross_bigger_boards_unchanged() {
+        let depth = Depth::new(7).unwrap();
+        let mut board = Board::empty(depth);
+        let centre = depth.centre();
+
+        board[centre].set_occupant(Rook::new(Player::White));
+        assert_eq!(board.legal_moves(centre).len(), 36);
+
+        board[centre].set_occupant(Queen::new(Player::White));
+        assert_eq!(board.legal_moves(centre).len(), 54);
+
+        let edge = Cell::new_in(depth, Rank::Rank1, File::FileN);
+        let moves = board.legal_moves(centre);
+        assert!(moves.iter().any(|mov| mov.move_to == edge));
+    }
+
     fn empty_board() -> Board {
-        Board::empty(0., 0., Depth::default(), FillMode::Wireframe, false)
+        Board::empty(Depth::default())
     }
 
     #[test]
     fn king_cannot_move_into_rook_attack() {
         let king_cell = Cell::new(Rank::Rank6, File::FileF);
         let target = Cell::new(Rank::Rank7, File::FileG);
-        let rook_cell = Cell::new(Rank::Rank9, File::FileG);
-        let mut board = empty_board();
-
-        board[king_cell].set_occupant(King::new(Color::White));
-        board[rook_cell].set_occupant(Rook::new(Color::Black));
+        let mut board = board_set!(
+            (Player::White, king, King) on [Rank::Rank6, File::FileF],
+            (Player::Black, rook, Rook) on [Rank::Rank9, File::FileG],
+        );
 
-        assert!(!board.is_in_check(Color::White));
+        assert!(!board.is_in_check(Player::White));
         assert!(
             !board
                 .legal_moves(king_cell)
@@ -604,11 +586,11 @@ mod tests {
         let attacker_cell = Cell::new(Rank::Rank9, File::FileF);
         let mut board = empty_board();
 
-        board[king_cell].set_occupant(King::new(Color::White));
-        board[pinned_cell].set_occupant(Rook::new(Color::White));
-        board[attacker_cell].set_occupant(Rook::new(Color::Black));
+        board[king_cell].set_occupant(King::new(Player::White));
+        board[pinned_cell].set_occupant(Rook::new(Player::White));
+        board[attacker_cell].set_occupant(Rook::new(Player::Black));
 
-        assert!(!board.is_in_check(Color::White));
+        assert!(!board.is_in_check(Player::White));
         assert!(
             !board
                 .legal_moves(pinned_cell)
@@ -624,15 +606,13 @@ mod tests {
         let enemy_king_cell = Cell::new(Rank::Rank9, File::FileF);
         let mut board = empty_board();
 
-        board[king_cell].set_occupant(King::new(Color::White));
-        board[rook_cell].set_occupant(Rook::new(Color::White));
-        board[enemy_king_cell].set_occupant(King::new(Color::Black));
+        board[king_cell].set_occupant(King::new(Player::White));
+        board[rook_cell].set_occupant(Rook::new(Player::White));
+        board[enemy_king_cell].set_occupant(King::new(Player::Black));
 
         assert!(
-            board[rook_cell]
-                .occupant()
-                .unwrap()
-                .valid_moves(&board, rook_cell)
+            board
+                .pseudo_legal_moves(rook_cell)
                 .iter()
                 .any(|mov| mov.move_to == enemy_king_cell)
         );
@@ -650,38 +630,179 @@ mod tests {
         let white_pawn = Cell::new(Rank::Rank6, File::FileF);
         let black_pawn = Cell::new(Rank::Rank5, File::FileG);
         let black_rook = Cell::new(Rank::Rank9, File::FileF);
-        let capture_move_to = Cell::new(Rank::Rank6, File::FileG);
+        let first_passed = Cell::new(Rank::Rank6, File::FileG);
         let mut board = empty_board();
 
-        board[white_king].set_occupant(King::new(Color::White));
-        board[white_pawn].set_occupant(Pawn::new(Color::White));
-        board[black_pawn].set_occupant(Pawn::new(Color::Black));
-        board[black_rook].set_occupant(Rook::new(Color::Black));
+        board[white_king].set_occupant(King::new(Player::White));
+        board[white_pawn].set_occupant(Pawn::new(Player::White));
+        board[black_pawn].set_occupant(Pawn::new(Player::Black));
+        board[black_rook].set_occupant(Rook::new(Player::Black));
         board.set_en_passant(EnPassant {
             captured_pawn: black_pawn,
-            capture_move_to,
-            pawn_color: Color::Black,
+            first_passed,
+            pawn_color: Player::Black,
         });
 
-        assert!(
-            board[white_pawn]
-                .occupant()
-                .unwrap()
-                .valid_moves(&board, white_pawn)
-                .iter()
-                .any(|mov| {
-                    mov.move_to == capture_move_to
-                        && matches!(
-                            mov.move_type,
-                            MoveType::Pawn(PawnMoveType::EnPassant { .. })
-                        )
-                })
-        );
+        assert!(board.pseudo_legal_moves(white_pawn).iter().any(|mov| {
+            mov.move_to == first_passed
+                && matches!(
+                    mov.move_type,
+                    MoveType::Pawn(PawnMoveType::EnPassant { .. })
+                )
+        }));
         assert!(
             !board
                 .legal_moves(white_pawn)
                 .iter()
-                .any(|mov| mov.move_to == capture_move_to)
+                .any(|mov| mov.move_to == first_passed)
+        );
+    }
+
+    #[test]
+    fn long_shafran_runs_can_be_taken_on_every_cell_passed() {
+        let src = Cell::new(Rank::Rank3, File::FileB);
+        let dest = Cell::new(Rank::Rank6, File::FileB);
+        let passed = [
+            Cell::new(Rank::Rank4, File::FileB),
+            Cell::new(Rank::Rank5, File::FileB),
+        ];
+        let mut board = empty_board();
+        board.set_variant(Variant::Shafran);
+        board[src].set_occupant(Pawn::new(Player::White));
+        board[Cell::new(Rank::Rank1, File::FileF)].set_occupant(King::new(Player::White));
+        board[Cell::new(Rank::Rank11, File::FileF)].set_occupant(King::new(Player::Black));
+
+        let run = board
+            .pseudo_legal_moves(src)
+            .into_iter()
+            .find(|mov| mov.move_to == dest)
+            .unwrap();
+        let _ = board.make_move(src, run);
+
+        for cell in passed {
+            let attacker = cell.next(Direction::Clock2).unwrap();
+            board[attacker].set_occupant(Pawn::new(Player::Black));
+            assert!(board.pseudo_legal_moves(attacker).iter().any(|mov| {
+                mov.move_to == cell
+                    && matches!(
+                        mov.move_type,
+                        MoveType::Pawn(PawnMoveType::EnPassant { remove_piece_on }) if remove_piece_on == dest
+                    )
+            }));
+            board[attacker].remove_occupant();
+        }
+        assert_eq!(board.en_passant_capture(Player::Black, dest), None);
+
+        let (parsed, player) = Board::from_fen(&board.to_fen(Player::Black)).unwrap();
+        for cell in passed {
+            assert_eq!(parsed.en_passant_capture(player, cell), Some(dest));
+        }
+    }
+
+    #[test]
+    fn kings_castle_on_both_wings_when_the_path_is_clear() {
+        let king = Cell::new(Rank::Rank2, File::FileD);
+        let mut board = castling_board("1R4/7/8/1K7/10/11/R9/7k1/8/7/6 w KQ - 0 1");
+        assert_eq!(castles(&mut board, king), [Wing::King, Wing::Queen]);
+
+        let castle = board
+            .legal_moves(king)
+            .into_iter()
+            .find(|mov| {
+                matches!(
+                    mov.move_type,
+                    MoveType::Rest(GeneralMoveType::Castle { .. })
+                )
+            })
+            .unwrap();
+        assert_eq!(castle.move_to, Cell::new(Rank::Rank2, File::FileF));
+
+        let rook = board.with_move_applied(king, castle, |board| {
+            assert!(board.castling().is_empty());
+            board[Cell::new(Rank::Rank2, File::FileE)]
+                .occupant()
+                .map(|piece| piece.ty())
+        });
+        assert_eq!(rook, Some(PieceType::Rook));
+        assert!(
+            board[Cell::new(Rank::Rank1, File::FileG)]
+                .occupant()
+                .is_some()
+        );
+        assert!(
+            board[Cell::new(Rank::Rank2, File::FileE)]
+                .occupant()
+                .is_none()
         );
+        assert_eq!(board.castling().label(), "KQ");
+
+        let mut blocked = castling_board("1R4/1N5/8/1K7/10/11/R9/7k1/8/7/6 w KQ - 0 1");
+        assert_eq!(castles(&mut blocked, king), [Wing::King]);
+
+        let mut no_rights = castling_board("1R4/7/8/1K7/10/11/R9/7k1/8/7/6 w Q - 0 1");
+        assert_eq!(castles(&mut no_rights, king), [Wing::Queen]);
+    }
+
+    #[test]
+    fn kings_cannot_castle_out_of_or_through_check() {
+        let king = Cell::new(Rank::Rank2, File::FileD);
+
+        let mut through = castling_board("1R4/7/8/1K7/9r/11/R9/7k1/8/7/6 w KQ - 0 1");
+        assert!(!through.is_in_check(Player::White));
+        assert_eq!(castles(&mut through, king), [Wing::Queen]);
+
+        let mut checked = castling_board("1R4/7/8/1K6r/10/11/R9/7k1/8/7/6 w KQ - 0 1");
+        assert!(checked.is_in_check(Player::White));
+        assert!(castles(&mut checked, king).is_empty());
+    }
+
+    #[test]
+    fn moving_a_king_or_rook_gives_up_castling() {
+        let mut board = castling_board("1R4/7/8/1K7/10/11/R9/7k1/8/7/6 w KQ - 0 1");
+        let king = Cell::new(Rank::Rank2, File::FileD);
+        let rook = Cell::new(Rank::Rank1, File::FileG);
+
+        let rook_move = Cell::new(Rank::Rank2, File::FileG);
+        assert_eq!(board.castling_after(rook, rook_move).label(), "Q");
+        assert_eq!(
+            board
+                .castling_after(king, Cell::new(Rank::Rank3, File::FileD))
+                .label(),
+            "-"
+        );
+
+        let step = Move::new(rook_move, MoveType::Rest(GeneralMoveType::NonCapture));
+        let after = board.with_move_applied(rook, step, |board| board.castling().label());
+        assert_eq!(after, "Q");
+    }
+
+    #[test]
+    fn unmaking_a_move_restores_the_position() {
+        let mut board = castling_board("1R4/7/8/1K7/10/11/R9/7k1/8/7/6 w KQ - 0 1");
+        let white_pawn = Cell::new(Rank::Rank6, File::FileF);
+        let black_pawn = Cell::new(Rank::Rank5, File::FileG);
+        let promoting = Cell::new(Rank::Rank6, File::FileB);
+        board[white_pawn].set_occupant(Pawn::new(Player::White));
+        board[black_pawn].set_occupant(Pawn::new(Player::Black));
+        board[promoting].set_occupant(Pawn::new(Player::White));
+        board.set_en_passant(EnPassant {
+            captured_pawn: black_pawn,
+            first_passed: Cell::new(Rank::Rank6, File::FileG),
+            pawn_color: Player::Black,
+        });
+        let fen = board.to_fen(Player::White);
+
+        let mut promoted = vec![];
+        for src in board.occupied_cells(Player::White) {
+            for mov in board.legal_moves(src) {
+                let undo = board.make_move(src, mov);
+                if mov.move_type.is_promotion() {
+                    promoted.push(board[mov.move_to].occupant().unwrap().ty());
+                }
+                board.unmake_move(undo);
+                assert_eq!(board.to_fen(Player::White), fen, "{} {mov:?}", src.label());
+            }
+        }
+        assert_eq!(promoted, PieceType::PROMOTIONS);
     }
 }
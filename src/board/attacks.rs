@@ -0,0 +1,213 @@
+use std::sync::OnceLock;
+
+use crate::{
+    unit::cell::Cell,
+    utils::{depth::Depth, direction::Direction, file::File, player::Player, rank::Rank},
+};
+
+/// Where every piece can reach from every cell of a board of one depth,
+/// ignoring what stands in the way. Cells are numbered file by file from
+/// `A1`, the same order the board keeps its entries in.
+#[derive(Debug)]
+pub(crate) struct AttackTables {
+    file_offsets: Vec<usize>,
+    cells: Vec<Cell>,
+    // The cells met going outwards in each direction, indexed by
+    // `Direction as usize`.
+    rays: Vec<[Vec<u16>; 12]>,
+    knight: Vec<Vec<u16>>,
+    king: Vec<Vec<u16>>,
+    // The cells a pawn of each side would have to stand on to attack a cell,
+    // White's first.
+    pawn_attackers: [Vec<Vec<u16>>; 2],
+}
+
+impl AttackTables {
+    /// The tables for a board of `depth`, built the first time they are
+    /// asked for.
+    pub(crate) fn for_depth(depth: Depth) -> &'static Self {
+        static TABLES: [OnceLock<AttackTables>; Depth::MAX as usize] =
+            [const { OnceLock::new() }; Depth::MAX as usize];
+
+        TABLES[depth.radius() as usize].get_or_init(|| Self::new(depth))
+    }
+
+    fn new(depth: Depth) -> Self {
+        let file_offsets = depth
+            .file_range()
+            .scan(0, |offset, file| {
+                let start = *offset;
+                *offset += depth.rank_range(file).remaining() as usize;
+                Some(start)
+            })
+            .collect::<Vec<_>>();
+        let cells = depth
+            .file_range()
+            .flat_map(|file| {
+                depth
+                    .rank_range(file)
+                    .map(move |rank| Cell::new_in(depth, rank, file))
+            })
+            .collect::<Vec<_>>();
+
+        let index = |cell: Cell| (file_offsets[cell.file.index()] + cell.rank.index()) as u16;
+        let step = |cell: Cell, directions: &[Direction]| {
+            directions
+                .iter()
+                .try_fold(cell, |cell, direction| cell.next(*direction))
+        };
+
+        let rays = cells
+            .iter()
+            .map(|cell| {
+                Direction::ALL.map(|direction| {
+                    std::iter::successors(cell.next(direction), |cell| cell.next(direction))
+                        .map(index)
+                        .collect()
+                })
+            })
+            .collect();
+        // Two steps through an edge, then one to either side of that line.
+        let knight = cells
+            .iter()
+            .map(|cell| {
+                Direction::ORTHOGONAL
+                    .into_iter()
+                    .flat_map(|direction| {
+                        [
+                            direction.turn_clockwise(),
+                            direction.turn_counter_clockwise(),
+                        ]
+                        .map(|side| step(*cell, &[direction, direction, side]))
+                    })
+                    .flatten()
+                    .map(index)
+                    .collect()
+            })
+            .collect();
+        let king = cells
+            .iter()
+            .map(|cell| {
+                Direction::ALL
+                    .into_iter()
+                    .filter_map(|direction| cell.next(direction))
+                    .map(index)
+                    .collect()
+            })
+            .collect();
+        let pawn_attackers = [Player::White, Player::Black].map(|player| {
+            let forward = pawn_forward(player);
+            cells
+                .iter()
+                .map(|cell| {
+                    [forward.turn_clockwise(), forward.turn_counter_clockwise()]
+                        .into_iter()
+                        .filter_map(|capture| cell.next(capture.reverse()))
+                        .map(index)
+                        .collect()
+                })
+                .collect()
+        });
+
+        Self {
+            file_offsets,
+            cells,
+            rays,
+            knight,
+            king,
+            pawn_attackers,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The cell numbered `index`, the inverse of [`AttackTables::index`].
+    pub(crate) fn cell(&self, index: usize) -> Cell {
+        self.cells[index]
+    }
+
+    pub(crate) fn index(&self, rank: Rank, file: File) -> usize {
+        self.file_offsets[file.index()] + rank.index()
+    }
+
+    pub(crate) fn ray(&self, index: usize, direction: Direction) -> &[u16] {
+        &self.rays[index][direction as usize]
+    }
+
+    pub(crate) fn knight(&self, index: usize) -> &[u16] {
+        &self.knight[index]
+    }
+
+    pub(crate) fn king(&self, index: usize) -> &[u16] {
+        &self.king[index]
+    }
+
+    pub(crate) fn pawn_attackers(&self, index: usize, player: Player) -> &[u16] {
+        &self.pawn_attackers[player as usize][index]
+    }
+}
+
+pub(crate) fn pawn_forward(player: Player) -> Direction {
+    match player {
+        Player::White => Direction::Clock12,
+        Player::Black => Direction::Clock6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        board::Board,
+        utils::{depth::Depth, file::File, player::Player, rank::Rank},
+    };
+
+    use super::AttackTables;
+
+    fn cells(board: &Board, indices: &[u16]) -> Vec<String> {
+        let mut labels = indices
+            .iter()
+            .map(|idx| board.tables.cell(*idx as usize).label())
+            .collect::<Vec<_>>();
+        labels.sort();
+        labels
+    }
+
+    #[test]
+    fn cells_are_numbered_in_board_order() {
+        for raw in 1..=Depth::MAX {
+            let depth = Depth::new(raw).unwrap();
+            let tables = AttackTables::for_depth(depth);
+            let board = Board::empty(depth);
+
+            assert_eq!(tables.len(), 3 * raw as usize * (raw as usize - 1) + 1);
+            assert_eq!(board.inner.len(), tables.len());
+            for idx in 0..tables.len() {
+                let cell = board.tables.cell(idx);
+                assert_eq!(tables.index(cell.rank, cell.file), idx);
+            }
+        }
+    }
+
+    #[test]
+    fn pawns_attack_from_behind_either_side() {
+        let board = Board::empty(Depth::GLINSKI);
+        let tables = AttackTables::for_depth(Depth::GLINSKI);
+        let f6 = tables.index(Rank::Rank6, File::FileF);
+        let a1 = tables.index(Rank::Rank1, File::FileA);
+
+        assert_eq!(
+            cells(&board, tables.pawn_attackers(f6, Player::White)),
+            ["E5", "G5"]
+        );
+        assert_eq!(
+            cells(&board, tables.pawn_attackers(f6, Player::Black)),
+            ["E6", "G6"]
+        );
+        assert_eq!(
+            cells(&board, tables.pawn_attackers(a1, Player::White)),
+            ["B1"]
+        );
+    }
+}
@@ -0,0 +1,422 @@
+use anyhow::{Result, anyhow, bail};
+
+use crate::{
+    pieces::PieceType,
+    unit::cell::Cell,
+    utils::{castling::CastlingRights, depth::Depth, player::Player},
+};
+
+use super::{Board, EnPassant, attacks::pawn_forward};
+
+#[cfg(any(test, feature = "tui"))]
+pub(crate) const STARTING_FEN: &str =
+    "6/P5p/RP4pr/N1P3p1n/Q2P2p2q/BBB1P1p1bbb/K2P2p2k/N1P3p1n/RP4pr/P5p/6";
+
+/// How FEN, and the move notation built on it, write a cell: `e5`.
+pub(crate) fn cell_label(cell: Cell) -> String {
+    format!(
+        "{}{}",
+        cell.file.label().to_ascii_lowercase(),
+        cell.rank.label()
+    )
+}
+
+impl Board {
+    /// The position in `fen` and the player to move in it, as written by
+    /// [`Board::to_fen`]: files `a` to `l` separated by `/`, each from rank 1
+    /// up with digits for runs of empty cells, then the side to move,
+    /// optional castling rights, en passant cells and the move counters.
+    ///
+    /// Fails with a description when a file has the wrong number of cells, a
+    /// piece letter is unknown or a side does not have exactly one king.
+    pub fn from_fen(fen: &str) -> Result<(Self, Player)> {
+        let fields = fen.split_whitespace().collect::<Vec<_>>();
+        // Castling rights are only written for variants that castle, so the
+        // field is optional and sits where standard chess FEN puts it.
+        let (placement, player, castling, en_passant, halfmove_clock, fullmove_number) =
+            match fields[..] {
+                [
+                    placement,
+                    player,
+                    en_passant,
+                    halfmove_clock,
+                    fullmove_number,
+                ] => (
+                    placement,
+                    player,
+                    "-",
+                    en_passant,
+                    halfmove_clock,
+                    fullmove_number,
+                ),
+                [
+                    placement,
+                    player,
+                    castling,
+                    en_passant,
+                    halfmove_clock,
+                    fullmove_number,
+                ] => (
+                    placement,
+                    player,
+                    castling,
+                    en_passant,
+                    halfmove_clock,
+                    fullmove_number,
+                ),
+                _ => bail!(
+                    "FEN must have 5 space separated fields (6 with castling rights), found {}: {fen:?}",
+                    fields.len()
+                ),
+            };
+
+        // A board of radius r has 2r + 1 files, so the placement alone tells
+        // us how big the board is.
+        let groups = placement.split('/').collect::<Vec<_>>();
+        let depth = u8::try_from(groups.len().div_ceil(2))
+            .ok()
+            .filter(|_| groups.len() % 2 == 1)
+            .and_then(|raw| Depth::new(raw).ok())
+            .ok_or_else(|| {
+                anyhow!(
+                    "FEN placement must describe an odd number of files up to {}, e.g. 11 files for the Glinski board, found {}",
+                    2 * Depth::MAX - 1,
+                    groups.len()
+                )
+            })?;
+        let mut board = Self::empty(depth);
+
+        for (file, group) in depth.file_range().zip(groups) {
+            let ranks = depth.rank_range(file).collect::<Vec<_>>();
+            let mut idx = 0_usize;
+            let mut chars = group.chars().peekable();
+
+            while let Some(c) = chars.next() {
+                if c.is_ascii_digit() {
+                    let too_long =
+                        || anyhow!("Empty run on file {} is longer than the file", file.label());
+                    let mut run = c.to_digit(10).unwrap_or_default() as usize;
+                    while let Some(digit) = chars.peek().and_then(|next| next.to_digit(10)) {
+                        run = run
+                            .checked_mul(10)
+                            .and_then(|run| run.checked_add(digit as usize))
+                            .ok_or_else(too_long)?;
+                        chars.next();
+                    }
+
+                    if run == 0 {
+                        bail!("Empty run of length 0 on file {}", file.label());
+                    }
+
+                    idx = idx.checked_add(run).ok_or_else(too_long)?;
+                    if idx > ranks.len() {
+                        bail!(
+                            "File {} describes {idx} cells, expected {}",
+                            file.label(),
+                            ranks.len()
+                        );
+                    }
+                    continue;
+                }
+
+                let piece_type = PieceType::from_label(&c.to_ascii_uppercase().to_string())
+                    .ok_or_else(|| {
+                        anyhow!("Invalid piece letter {c:?} on file {}", file.label())
+                    })?;
+                let color = if c.is_ascii_uppercase() {
+                    Player::White
+                } else {
+                    Player::Black
+                };
+
+                if let Some(rank) = ranks.get(idx) {
+                    board[Cell::new_in(depth, *rank, file)].replace_occupant((piece_type, color));
+                }
+                idx += 1;
+            }
+
+            if idx != ranks.len() {
+                bail!(
+                    "File {} describes {idx} cells, expected {}",
+                    file.label(),
+                    ranks.len()
+                );
+            }
+        }
+
+        for (color, name) in [(Player::White, "white"), (Player::Black, "black")] {
+            let kings = board
+                .occupied_cells(color)
+                .into_iter()
+                .filter(|cell| {
+                    board[*cell]
+                        .occupant()
+                        .is_some_and(|piece| piece.ty() == PieceType::King)
+                })
+                .count();
+
+            if kings != 1 {
+                bail!("Expected exactly one {name} king, found {kings}");
+            }
+        }
+
+        let player = match player {
+            "w" => Player::White,
+            "b" => Player::Black,
+            _ => bail!("Side to move must be 'w' or 'b', found {player:?}"),
+        };
+
+        board.castling = CastlingRights::from_label(castling)
+            .ok_or_else(|| anyhow!("Invalid castling rights {castling:?}"))?;
+
+        if en_passant != "-" {
+            let first_passed = Cell::from_label_in(depth, en_passant)
+                .map_err(|_| anyhow!("Invalid en passant cell {en_passant:?}"))?;
+
+            // Only the first cell passed over is written; the rest of the run
+            // lies between it and the pawn.
+            let pawn_color = player.toggle();
+            let forward = pawn_forward(pawn_color);
+            let captured_pawn = std::iter::successors(first_passed.next(forward), |cell| {
+                cell.next(forward)
+            })
+            .find(|cell| board[*cell].occupant().is_some())
+            .filter(|cell| {
+                board[*cell].occupant().is_some_and(|piece| {
+                    piece.ty() == PieceType::Pawn && piece.color() == pawn_color
+                })
+            })
+            .ok_or_else(|| {
+                anyhow!("En passant cell {en_passant:?} is not behind a pawn that just ran past it")
+            })?;
+
+            board.set_en_passant(EnPassant {
+                captured_pawn,
+                first_passed,
+                pawn_color,
+            });
+        }
+
+        board.halfmove_clock = halfmove_clock
+            .parse()
+            .map_err(|_| anyhow!("Invalid half-move clock {halfmove_clock:?}"))?;
+        board.fullmove_number = fullmove_number
+            .parse()
+            .ok()
+            .filter(|number| *number > 0)
+            .ok_or_else(|| anyhow!("Invalid full-move number {fullmove_number:?}"))?;
+
+        Ok((board, player))
+    }
+
+    pub fn to_fen(&self, player: Player) -> String {
+        let placement = self
+            .depth
+            .file_range()
+            .map(|file| {
+                let mut group = String::new();
+                let mut run = 0;
+
+                for rank in self.depth.rank_range(file) {
+                    match self[Cell::new_in(self.depth, rank, file)].occupant() {
+                        Some(piece) => {
+                            if run > 0 {
+                                group.push_str(&run.to_string());
+                                run = 0;
+                            }
+
+                            let label = piece.ty().label();
+                            if piece.color() == Player::White {
+                                group.push_str(label);
+                            } else {
+                                group.push_str(&label.to_ascii_lowercase());
+                            }
+                        }
+                        None => run += 1,
+                    }
+                }
+
+                if run > 0 {
+                    group.push_str(&run.to_string());
+                }
+
+                group
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let player = match player {
+            Player::White => "w",
+            Player::Black => "b",
+        };
+
+        let en_passant = self.en_passant.map_or("-".to_string(), |en_passant| {
+            cell_label(en_passant.first_passed)
+        });
+
+        let castling = if self.castling.is_empty() {
+            String::new()
+        } else {
+            format!("{} ", self.castling.label())
+        };
+
+        format!(
+            "{placement} {player} {castling}{en_passant} {} {}",
+            self.halfmove_clock, self.fullmove_number
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        board::Board,
+        pieces::PieceType,
+        unit::cell::Cell,
+        utils::{depth::Depth, file::File, player::Player, rank::Rank},
+    };
+
+    use super::STARTING_FEN;
+
+    #[test]
+    fn starting_position_round_trips() {
+        let board = Board::new();
+        let fen = board.to_fen(Player::White);
+
+        assert_eq!(fen, format!("{STARTING_FEN} w - 0 1"));
+
+        let (parsed, player) = Board::from_fen(&fen).unwrap();
+        assert!(matches!(player, Player::White));
+        assert_eq!(parsed.to_fen(player), fen);
+    }
+
+    #[test]
+    fn en_passant_target_and_counters_round_trip() {
+        let mut board = Board::new();
+        let src = Cell::new(Rank::Rank4, File::FileE);
+        let mov = board
+            .legal_moves(src)
+            .into_iter()
+            .find(|mov| mov.move_to == Cell::new(Rank::Rank6, File::FileE))
+            .unwrap();
+        let _ = board.make_move(src, mov);
+
+        let fen = board.to_fen(Player::Black);
+        assert!(fen.ends_with(" b e5 0 1"), "{fen}");
+
+        let (parsed, player) = Board::from_fen(&fen).unwrap();
+        assert_eq!(parsed.to_fen(player), fen);
+        assert_eq!(
+            parsed.en_passant_capture(Player::Black, Cell::new(Rank::Rank5, File::FileE)),
+            Some(Cell::new(Rank::Rank6, File::FileE))
+        );
+    }
+
+    #[test]
+    fn castling_rights_are_an_optional_field() {
+        let fen = "1R4/7/8/1K7/10/11/R9/7k1/8/7/6 b Kq - 3 9";
+        let (board, player) = Board::from_fen(fen).unwrap();
+
+        assert_eq!(board.castling().label(), "Kq");
+        assert_eq!(board.to_fen(player), fen);
+
+        let (board, player) = Board::from_fen("1R4/7/8/1K7/10/11/R9/7k1/8/7/6 b - - 3 9").unwrap();
+        assert!(board.castling().is_empty());
+        assert_eq!(
+            board.to_fen(player),
+            "1R4/7/8/1K7/10/11/R9/7k1/8/7/6 b - 3 9"
+        );
+    }
+
+    #[test]
+    fn placement_describes_pieces_file_by_file() {
+        let (board, _) = Board::from_fen("6/7/8/9/10/5K5/10/9/8/7/5k w - 12 40").unwrap();
+
+        let king = board[Cell::new(Rank::Rank6, File::FileF)]
+            .occupant()
+            .expect("white king should be placed");
+        assert_eq!(king.ty(), PieceType::King);
+        assert_eq!(king.color(), Player::White);
+        assert!(
+            board[Cell::new(Rank::Rank6, File::FileL)]
+                .occupant()
+                .is_some()
+        );
+        assert_eq!(
+            board.to_fen(Player::White),
+            "6/7/8/9/10/5K5/10/9/8/7/5k w - 12 40"
+        );
+    }
+
+    #[test]
+    fn board_size_follows_the_number_of_files() {
+        let fen = "7/8/9/10/11/12/6K6/12/11/10/9/8/6k w - 0 1";
+        let (board, player) = Board::from_fen(fen).unwrap();
+
+        assert_eq!(board.depth(), Depth::new(7).unwrap());
+        assert!(
+            board[board.depth().centre()]
+                .occupant()
+                .is_some_and(|piece| piece.ty() == PieceType::King)
+        );
+        assert_eq!(board.to_fen(player), fen);
+    }
+
+    #[test]
+    fn malformed_fen_strings_are_rejected_with_reasons() {
+        let cases = [
+            (
+                "6/7/8/9/10/5K5/10/9/8/7/5k w - 0",
+                "5 space separated fields",
+            ),
+            ("6/7/8/9/10/5K5/10/9/8/5k w - 0 1", "11 files"),
+            (
+                "99999999999999999999999 w - 0 1",
+                "Empty run on file A is longer than the file",
+            ),
+            (
+                "6/7/8/9/10/5K5/10/9/8/7/18446744073709551615k w - 0 1",
+                "describes 18446744073709551615 cells, expected 6",
+            ),
+            (
+                "6/7/8/9/10/5K5/10/9/8/7/1k18446744073709551615 w - 0 1",
+                "Empty run on file L is longer than the file",
+            ),
+            (
+                "6/7/8/9/10/5K6/10/9/8/7/5k w - 0 1",
+                "describes 12 cells, expected 11",
+            ),
+            (
+                "6/7/8/9/10/5K4/10/9/8/7/5k w - 0 1",
+                "describes 10 cells, expected 11",
+            ),
+            (
+                "6/7/8/9/10/5X5/10/9/8/7/5k w - 0 1",
+                "Invalid piece letter 'X'",
+            ),
+            (
+                "6/7/8/9/10/5K5/10/9/8/7/6 w - 0 1",
+                "one black king, found 0",
+            ),
+            (
+                "6/7/8/9/10/5K5/10/9/8/7/K4k w - 0 1",
+                "one white king, found 2",
+            ),
+            ("6/7/8/9/10/5K5/10/9/8/7/5k x - 0 1", "Side to move"),
+            ("6/7/8/9/10/5K5/10/9/8/7/5k w e6 0 1", "En passant cell"),
+            ("6/7/8/9/10/5K5/10/9/8/7/5k w - a 1", "half-move clock"),
+            (
+                "6/7/8/9/10/5K5/10/9/8/7/5k w KX - 0 1",
+                "Invalid castling rights",
+            ),
+            ("6/7/8/9/10/5K5/10/9/8/7/5k w - 0 0", "full-move number"),
+        ];
+
+        for (fen, reason) in cases {
+            let err = Board::from_fen(fen)
+                .err()
+                .unwrap_or_else(|| panic!("{fen} should fail"));
+            assert!(err.to_string().contains(reason), "{fen}: {err}");
+        }
+    }
+}
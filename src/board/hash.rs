@@ -0,0 +1,158 @@
+use crate::{pieces::PieceType, utils::player::Player};
+
+use super::Board;
+
+const PIECE_KINDS: u64 = 12;
+
+// splitmix64, so every Zobrist key can be derived on demand instead of living
+// in a table sized for one particular board depth.
+fn key(index: u64) -> u64 {
+    let mut z = index.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn piece_index(piece_type: PieceType, color: Player) -> u64 {
+    let piece = match piece_type {
+        PieceType::Bishop => 0,
+        PieceType::King => 1,
+        PieceType::Knight => 2,
+        PieceType::Pawn => 3,
+        PieceType::Queen => 4,
+        PieceType::Rook => 5,
+    };
+
+    if color == Player::White {
+        piece
+    } else {
+        piece + 6
+    }
+}
+
+/// The Zobrist keys of a board with `cells` cells: every piece on every cell,
+/// then Black to move, then an en passant cell and the castling rights, each
+/// numbered on from where the one before ends.
+#[derive(Clone, Copy)]
+struct Keys {
+    cells: u64,
+}
+
+impl Keys {
+    fn piece(self, idx: usize, piece_type: PieceType, color: Player) -> u64 {
+        key(idx as u64 * PIECE_KINDS + piece_index(piece_type, color))
+    }
+
+    fn black_to_move(self) -> u64 {
+        key(self.cells * PIECE_KINDS)
+    }
+
+    fn en_passant(self, idx: usize) -> u64 {
+        key(self.cells * PIECE_KINDS + 1 + idx as u64)
+    }
+
+    fn castling(self, right: u64) -> u64 {
+        key(self.cells * (PIECE_KINDS + 1) + 1 + right)
+    }
+}
+
+impl Board {
+    fn keys(&self) -> Keys {
+        Keys {
+            cells: self.inner.len() as u64,
+        }
+    }
+
+    pub(crate) fn position_hash(&self, to_move: Player) -> u64 {
+        let keys = self.keys();
+        let pieces = self
+            .inner
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, entry)| {
+                let piece = entry.occupant()?;
+                Some(keys.piece(idx, piece.ty(), piece.color()))
+            })
+            .fold(0, |hash, key| hash ^ key);
+
+        let side = if to_move == Player::Black {
+            keys.black_to_move()
+        } else {
+            0
+        };
+
+        let en_passant = self.en_passant.map_or(0, |en_passant| {
+            keys.en_passant(self.board_index(en_passant.first_passed))
+        });
+
+        let castling = self
+            .castling
+            .held()
+            .fold(0, |hash, right| hash ^ keys.castling(right));
+
+        pieces ^ side ^ en_passant ^ castling
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{
+        board::Board,
+        pieces::PieceType,
+        utils::{depth::Depth, notation::parse_san, player::Player},
+    };
+
+    // White's and Black's, on each wing.
+    const CASTLING_RIGHTS: u64 = 4;
+
+    fn play(board: &mut Board, color: Player, san: &str) {
+        let (src, mov) = parse_san(board, color, san).unwrap();
+        let _ = board.make_move(src, mov);
+    }
+
+    #[test]
+    fn hash_tracks_placement_side_and_en_passant() {
+        let mut board = Board::new();
+        let start = board.position_hash(Player::White);
+        assert_ne!(start, board.position_hash(Player::Black));
+
+        play(&mut board, Player::White, "Nc3");
+        play(&mut board, Player::Black, "Nc6");
+        play(&mut board, Player::White, "Nd1");
+        play(&mut board, Player::Black, "Nd9");
+        assert_eq!(board.position_hash(Player::White), start);
+
+        play(&mut board, Player::White, "e6");
+        let with_en_passant = board.position_hash(Player::Black);
+        board.en_passant = None;
+        assert_ne!(board.position_hash(Player::Black), with_en_passant);
+    }
+
+    #[test]
+    fn no_two_keys_alias_on_the_biggest_board() {
+        let board = Board::empty(Depth::new(Depth::MAX).unwrap());
+        let keys = board.keys();
+        let cells = board.inner.len();
+
+        let pieces = (0..cells).flat_map(|idx| {
+            [Player::White, Player::Black]
+                .into_iter()
+                .flat_map(move |color| {
+                    PieceType::PROMOTIONS
+                        .into_iter()
+                        .chain([PieceType::King, PieceType::Pawn])
+                        .map(move |piece_type| keys.piece(idx, piece_type, color))
+                })
+        });
+        let all = pieces
+            .chain([keys.black_to_move()])
+            .chain((0..cells).map(|idx| keys.en_passant(idx)))
+            .chain((0..CASTLING_RIGHTS).map(|right| keys.castling(right)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(all.len(), cells * 13 + 1 + CASTLING_RIGHTS as usize);
+        assert_eq!(all.iter().collect::<HashSet<_>>().len(), all.len());
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{
+    pieces::PieceType,
+    unit::cell::Cell,
+    utils::{direction::Direction, player::Player},
+};
+
+use super::Board;
+
+// On the hexagonal board even a lone knight mates a king driven into one of
+// the six corners, e.g. White Kc3 with Nb4 against Ka1, and so does a lone
+// bishop, e.g. Bb3, as long as its shade has a corner for the king to be
+// caught in. A shade without corners, F3's on Glinski's board, leaves a
+// bishop on it unable to ever mate a bare king.
+impl Board {
+    fn material(&self, color: Player) -> Vec<(PieceType, Cell)> {
+        self.occupied_cells(color)
+            .into_iter()
+            .filter_map(|cell| {
+                let piece = self[cell].occupant()?;
+                (piece.ty() != PieceType::King).then(|| (piece.ty(), cell))
+            })
+            .collect()
+    }
+
+    // Which of the three tones `cell` has; a bishop never leaves its own.
+    fn shade(&self, cell: Cell) -> usize {
+        let last_file = self.depth.file_range().remaining() as usize - 1;
+        let file = cell.file.index();
+
+        (cell.rank.index() + file.min(last_file - file)) % 3
+    }
+
+    fn corners(&self) -> impl Iterator<Item = Cell> + '_ {
+        let centre = self.depth.centre();
+
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |direction| {
+                std::iter::successors(Some(centre), |cell| self.next(*cell, direction)).last()
+            })
+    }
+
+    // Whether no series of legal moves lets `color` mate: they have a bare
+    // king, or a lone bishop on a shade without corners against one.
+    fn cannot_mate(&self, color: Player) -> bool {
+        match self.material(color)[..] {
+            [] => true,
+            [(PieceType::Bishop, bishop)] => {
+                self.material(color.toggle()).is_empty()
+                    && self
+                        .corners()
+                        .all(|corner| self.shade(corner) != self.shade(bishop))
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether `color` could still mate, so the opponent running out of time
+    /// still loses to them.
+    pub fn has_mating_material(&self, color: Player) -> bool {
+        !self.cannot_mate(color)
+    }
+
+    /// Whether neither side can mate any more, which makes the game a draw.
+    pub fn has_insufficient_material(&self) -> bool {
+        self.cannot_mate(Player::White) && self.cannot_mate(Player::Black)
+    }
+}
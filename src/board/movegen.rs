@@ -0,0 +1,207 @@
+use crate::{
+    pieces::PieceType,
+    unit::cell::Cell,
+    utils::{
+        direction::Direction,
+        moves::{GeneralMoveType, Move, MoveType, PawnMoveType},
+        player::Player,
+    },
+};
+
+use super::{Board, attacks::pawn_forward};
+
+type Square = Option<(PieceType, Player)>;
+
+impl Board {
+    /// What stands on the cell numbered `idx` in the attack tables.
+    pub(super) fn square(&self, idx: usize) -> Square {
+        self.inner[idx].square()
+    }
+
+    fn at(&self, cell: Cell) -> Square {
+        self.square(self.board_index(cell))
+    }
+
+    fn king(&self, player: Player) -> Option<usize> {
+        (0..self.tables.len()).find(|idx| self.square(*idx) == Some((PieceType::King, player)))
+    }
+
+    pub fn is_in_check(&self, player: Player) -> bool {
+        self.king(player)
+            .is_some_and(|king| self.is_attacked(king, player.toggle()))
+    }
+
+    /// Whether any of `attacker`'s pieces could capture on the cell at `idx`.
+    fn is_attacked(&self, idx: usize, attacker: Player) -> bool {
+        let holds = |idx: &u16, types: &[PieceType]| {
+            self.square(*idx as usize)
+                .is_some_and(|(ty, player)| player == attacker && types.contains(&ty))
+        };
+        let slides = |directions: [Direction; 6], types: &[PieceType]| {
+            directions.into_iter().any(|direction| {
+                self.tables
+                    .ray(idx, direction)
+                    .iter()
+                    .find(|idx| self.square(**idx as usize).is_some())
+                    .is_some_and(|idx| holds(idx, types))
+            })
+        };
+
+        self.tables
+            .pawn_attackers(idx, attacker)
+            .iter()
+            .any(|idx| holds(idx, &[PieceType::Pawn]))
+            || self
+                .tables
+                .knight(idx)
+                .iter()
+                .any(|idx| holds(idx, &[PieceType::Knight]))
+            || self
+                .tables
+                .king(idx)
+                .iter()
+                .any(|idx| holds(idx, &[PieceType::King]))
+            || slides(Direction::ORTHOGONAL, &[PieceType::Rook, PieceType::Queen])
+            || slides(Direction::DIAGONAL, &[PieceType::Bishop, PieceType::Queen])
+    }
+
+    /// Every move the piece on `src` has, read off the attack tables, before
+    /// checking whether it leaves its own king in check.
+    pub(crate) fn pseudo_legal_moves(&self, src: Cell) -> Vec<Move> {
+        let Some((piece_type, color)) = self.at(src) else {
+            return vec![];
+        };
+        let idx = self.board_index(src);
+        let mut moves = vec![];
+
+        match piece_type {
+            PieceType::Pawn => self.pawn_moves(src, color, &mut moves),
+            PieceType::Knight => self.leaps(self.tables.knight(idx), color, &mut moves),
+            PieceType::King => {
+                self.leaps(self.tables.king(idx), color, &mut moves);
+                self.castles(src, color, &mut moves);
+            }
+            PieceType::Rook => self.slides(idx, &Direction::ORTHOGONAL, color, &mut moves),
+            PieceType::Bishop => self.slides(idx, &Direction::DIAGONAL, color, &mut moves),
+            PieceType::Queen => self.slides(idx, &Direction::ALL, color, &mut moves),
+        }
+
+        moves
+    }
+
+    fn pawn_moves(&self, src: Cell, color: Player, moves: &mut Vec<Move>) {
+        let idx = self.board_index(src);
+        let forward = pawn_forward(color);
+        let promotion_cells = self.variant.promotion_cells(color, self.depth);
+        let push = |moves: &mut Vec<Move>,
+                    dest: Cell,
+                    plain: PawnMoveType,
+                    promotion: fn(PieceType) -> PawnMoveType| {
+            if promotion_cells.contains(&dest) {
+                moves.extend(
+                    PieceType::PROMOTIONS
+                        .map(|promote_to| Move::new(dest, MoveType::Pawn(promotion(promote_to)))),
+                );
+            } else {
+                moves.push(Move::new(dest, MoveType::Pawn(plain)));
+            }
+        };
+
+        let steps = self.variant.pawn_steps(color, src, forward);
+        for dest in self.tables.ray(idx, forward).iter().take(steps) {
+            let dest = *dest as usize;
+            if self.square(dest).is_some() {
+                break;
+            }
+            push(
+                moves,
+                self.tables.cell(dest),
+                PawnMoveType::NonCapture,
+                |promote_to| PawnMoveType::NonCapturePromotion { promote_to },
+            );
+        }
+
+        for side in [forward.turn_clockwise(), forward.turn_counter_clockwise()] {
+            let Some(dest) = self.tables.ray(idx, side).first() else {
+                continue;
+            };
+            let (square, dest) = (
+                self.square(*dest as usize),
+                self.tables.cell(*dest as usize),
+            );
+
+            match square {
+                Some((_, other)) if other != color => {
+                    push(moves, dest, PawnMoveType::NormalCapture, |promote_to| {
+                        PawnMoveType::CapturePromotion { promote_to }
+                    });
+                }
+                Some(_) => {}
+                None => {
+                    if let Some(remove_piece_on) = self.en_passant_capture(color, dest) {
+                        moves.push(Move::new(
+                            dest,
+                            MoveType::Pawn(PawnMoveType::EnPassant { remove_piece_on }),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    // Whether the king may castle through an attacked cell is left to the
+    // legality check.
+    fn castles(&self, king: Cell, color: Player, moves: &mut Vec<Move>) {
+        for castling in self.variant.castlings(color) {
+            if castling.king != king
+                || !self.castling.has(color, castling.wing)
+                || self.at(castling.rook) != Some((PieceType::Rook, color))
+                || castling.between().any(|cell| self.at(cell).is_some())
+            {
+                continue;
+            }
+
+            if let (Some(king_to), Some(rook_to)) = (castling.king_to(), castling.rook_to()) {
+                moves.push(Move::new(
+                    king_to,
+                    MoveType::Rest(GeneralMoveType::Castle {
+                        wing: castling.wing,
+                        rook_from: castling.rook,
+                        rook_to,
+                    }),
+                ));
+            }
+        }
+    }
+
+    // A move to the cell numbered `dest` unless `color`'s own piece stands
+    // there, and whether anything does.
+    fn step(&self, dest: u16, color: Player, moves: &mut Vec<Move>) -> bool {
+        let dest = dest as usize;
+        let square = self.square(dest);
+        let move_type = match square {
+            None => GeneralMoveType::NonCapture,
+            Some((_, other)) if other != color => GeneralMoveType::Capture,
+            Some(_) => return true,
+        };
+        moves.push(Move::new(self.tables.cell(dest), MoveType::Rest(move_type)));
+
+        square.is_some()
+    }
+
+    fn leaps(&self, targets: &[u16], color: Player, moves: &mut Vec<Move>) {
+        for dest in targets {
+            self.step(*dest, color, moves);
+        }
+    }
+
+    fn slides(&self, idx: usize, directions: &[Direction], color: Player, moves: &mut Vec<Move>) {
+        for direction in directions {
+            for dest in self.tables.ray(idx, *direction) {
+                if self.step(*dest, color, moves) {
+                    break;
+                }
+            }
+        }
+    }
+}
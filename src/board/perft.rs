@@ -0,0 +1,124 @@
+use crate::{
+    unit::cell::Cell,
+    utils::{moves::Move, player::Player},
+};
+
+use super::{Board, fen::cell_label};
+
+impl Board {
+    fn all_legal_moves(&mut self, color: Player) -> Vec<(Cell, Move)> {
+        self.occupied_cells(color)
+            .into_iter()
+            .flat_map(|src| {
+                self.legal_moves(src)
+                    .into_iter()
+                    .map(move |mov| (src, mov))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    pub fn perft(&mut self, color: Player, depth: u8) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let moves = self.all_legal_moves(color);
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        moves
+            .into_iter()
+            .map(|(src, mov)| {
+                let undo = self.make_move(src, mov);
+                let nodes = self.perft(color.toggle(), depth - 1);
+                self.unmake_move(undo);
+                nodes
+            })
+            .sum()
+    }
+
+    pub fn divide(&mut self, color: Player, depth: u8) -> Vec<(String, u64)> {
+        self.all_legal_moves(color)
+            .into_iter()
+            .map(|(src, mov)| {
+                let mut label = format!("{}{}", cell_label(src), cell_label(mov.move_to));
+                if let Some(promote_to) = mov.move_type.promotion() {
+                    label.push_str(&promote_to.label().to_ascii_lowercase());
+                }
+
+                let undo = self.make_move(src, mov);
+                let nodes = self.perft(color.toggle(), depth.saturating_sub(1));
+                self.unmake_move(undo);
+
+                (label, nodes)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        board::Board,
+        unit::cell::Cell,
+        utils::{file::File, player::Player, rank::Rank},
+    };
+
+    fn start() -> Board {
+        Board::new()
+    }
+
+    // The counts below (51, 2586, 137_858 and 7_282_418) come from
+    // `scripts/perft.py`, a separate perft on cube coordinates that shares
+    // only the starting FEN with this board.
+
+    #[test]
+    fn start_position_matches_reference_counts() {
+        let mut board = start();
+
+        assert_eq!(board.perft(Player::White, 1), 51);
+        assert_eq!(board.perft(Player::White, 2), 2586);
+        assert_eq!(board.perft(Player::White, 3), 137_858);
+    }
+
+    // Takes a few seconds in a debug build, so run it with
+    // `cargo test --release -- --ignored`; `cargo bench` times it too.
+    #[test]
+    #[ignore]
+    fn start_position_matches_the_depth_4_count() {
+        assert_eq!(start().perft(Player::White, 4), 7_282_418);
+    }
+
+    #[test]
+    fn divide_sums_to_perft() {
+        let mut board = start();
+        let divide = board.divide(Player::White, 2);
+
+        assert_eq!(divide.len(), 51);
+        assert_eq!(divide.iter().map(|(_, nodes)| nodes).sum::<u64>(), 2586);
+
+        // e6 takes both pushes away from black's e-pawn but hands the f-pawn
+        // a capture.
+        let e4e6 = divide.iter().find(|(label, _)| label == "e4e6").unwrap();
+        assert_eq!(e4e6.1, 50);
+    }
+
+    #[test]
+    fn every_promotion_piece_is_counted() {
+        let (mut board, player) = Board::from_fen("6/7/8/9/8P1/5K5/10/9/8/7/5k w - 0 1").unwrap();
+        let color = player;
+
+        let promotions = board
+            .divide(color, 1)
+            .into_iter()
+            .filter(|(label, _)| label.starts_with("e9"))
+            .map(|(label, _)| label)
+            .collect::<Vec<_>>();
+        assert_eq!(promotions, ["e9e10q", "e9e10r", "e9e10b", "e9e10n"]);
+
+        let king_moves = board.legal_moves(Cell::new(Rank::Rank6, File::FileF)).len() as u64;
+        assert_eq!(board.perft(color, 1), king_moves + 4);
+    }
+}
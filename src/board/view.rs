@@ -0,0 +1,426 @@
+use ratatui::{
+    layout::{self, Rect},
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{
+        Block, Borders, Widget,
+        canvas::{Canvas, Painter, Shape},
+    },
+};
+
+use crate::{
+    hexagon::Hexagon,
+    pieces::{Piece, PieceType},
+    unit::cell::Cell,
+    utils::{
+        consts::{TERM_SCALE_FACTOR, TONE_CANVAS_BG, TONE_HEX_BG1, TONE_HEX_BG2, TONE_HEX_BG3},
+        delta::Delta,
+        direction::Direction,
+        fill_mode::FillMode,
+        mark::Mark,
+        mode::{HighlightMode, Status},
+        moves::{GeneralMoveType, Move, MoveType, PawnMoveType},
+        player::Player,
+    },
+};
+
+use super::Board;
+
+/// How big the cells are drawn and how they are filled in.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BoardStyle {
+    pub(crate) len: f64,
+    pub(crate) padding: f64,
+    pub(crate) fill_mode: FillMode,
+    pub(crate) hide_highlights: bool,
+}
+
+/// A board as the player sees it: turned to their side, with the cursor and
+/// the moves of the selected piece marked on it.
+pub(crate) struct BoardView<'a> {
+    pub(crate) board: &'a Board,
+    pub(crate) style: BoardStyle,
+    pub(crate) flipped: bool,
+    pub(crate) current: Cell,
+    pub(crate) moves: &'a [Move],
+    pub(crate) div: f64,
+    pub(crate) coordinates: bool,
+}
+
+impl BoardView<'_> {
+    fn bounds(&self, area: Rect) -> ([f64; 2], [f64; 2]) {
+        let y_dim = area.height as f64;
+        let x_dim = y_dim * TERM_SCALE_FACTOR;
+
+        (
+            [-x_dim / self.div, x_dim / self.div],
+            [-y_dim / 2., y_dim / 2.],
+        )
+    }
+
+    fn hexagon(&self, cell: Cell, color: Color, mode: HighlightMode) -> Hexagon {
+        let mut hex = Hexagon::new(
+            cell,
+            self.style.len,
+            self.style.padding,
+            color,
+            self.style.fill_mode,
+            mode,
+        );
+        hex.set_flipped(self.flipped);
+        hex
+    }
+
+    /// The cells the selected piece can move to, and the pawns it can take
+    /// en passant.
+    fn statuses(&self) -> Vec<(Cell, Status)> {
+        self.moves
+            .iter()
+            .flat_map(|mov| match mov.move_type {
+                MoveType::Rest(GeneralMoveType::Capture)
+                | MoveType::Pawn(PawnMoveType::CapturePromotion { .. })
+                | MoveType::Pawn(PawnMoveType::NormalCapture) => {
+                    vec![(mov.move_to, Status::Capturable)]
+                }
+                MoveType::Rest(GeneralMoveType::NonCapture | GeneralMoveType::Castle { .. })
+                | MoveType::Pawn(PawnMoveType::NonCapturePromotion { .. })
+                | MoveType::Pawn(PawnMoveType::NonCapture) => vec![(mov.move_to, Status::Movable)],
+                MoveType::Pawn(PawnMoveType::EnPassant { remove_piece_on }) => vec![
+                    (mov.move_to, Status::Movable),
+                    (remove_piece_on, Status::Capturable),
+                ],
+            })
+            .collect()
+    }
+
+    /// Every cell's hexagon, shaded so that no two neighbours share a tone.
+    fn hexagons(&self) -> Vec<Hexagon> {
+        let colors = [TONE_HEX_BG1, TONE_HEX_BG2, TONE_HEX_BG3];
+        let depth = self.board.depth();
+        let num_files = depth.file_range().remaining() as usize;
+        let statuses = self.statuses();
+
+        depth
+            .file_range()
+            .enumerate()
+            .flat_map(|(idx, file)| {
+                depth
+                    .rank_range(file)
+                    .zip(
+                        colors
+                            .into_iter()
+                            .cycle()
+                            .skip(idx.min((num_files - 1) - idx)),
+                    )
+                    .map(move |(rank, color)| (Cell::new_in(depth, rank, file), color))
+            })
+            .map(|(cell, color)| {
+                let status = statuses
+                    .iter()
+                    .find(|(other, _)| *other == cell)
+                    .map_or(Status::None, |(_, status)| *status);
+
+                self.hexagon(
+                    cell,
+                    color,
+                    HighlightMode::new(cell == self.current, status),
+                )
+            })
+            .collect()
+    }
+
+    /// The cell whose hexagon contains `point` in canvas coordinates. Hexagons
+    /// tile the plane, so that is the one with the nearest centre.
+    fn cell_under(&self, point: Delta<f64>) -> Option<Cell> {
+        let distance = |hex: &Hexagon| {
+            let center = hex.center();
+            (center.x - point.x).hypot(center.y - point.y)
+        };
+
+        self.hexagons()
+            .into_iter()
+            .min_by(|lhs, rhs| distance(lhs).total_cmp(&distance(rhs)))
+            .filter(|hex| distance(hex) <= hex.len())
+            .map(|hex| hex.cell())
+    }
+
+    /// The cell drawn under the terminal cell at `column`, `row` when the
+    /// view is rendered into `area`.
+    pub(crate) fn cell_at(&self, area: Rect, column: u16, row: u16) -> Option<Cell> {
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        if !inner.contains(layout::Position::new(column, row)) {
+            return None;
+        }
+
+        // Canvas coordinates of the middle of the terminal cell; the canvas
+        // puts its y axis upwards while rows count down.
+        let ([left, right], [bottom, top]) = self.bounds(area);
+        let x =
+            left + (f64::from(column - inner.x) + 0.5) * (right - left) / f64::from(inner.width);
+        let y = top - (f64::from(row - inner.y) + 0.5) * (top - bottom) / f64::from(inner.height);
+
+        self.cell_under(Delta::new(x, y))
+    }
+
+    /// File letters under the bottom edges and rank numbers along the left
+    /// sides, each where the next cell off the board would be drawn. Ranks
+    /// past the middle end on the upper left edge, so their labels go up and
+    /// to the left rather than down.
+    fn coordinate_labels(&self) -> Vec<(Delta<f64>, String)> {
+        let depth = self.board.depth();
+        // Seen from Black's side the far edges of the board face the player.
+        let (file_off, rank_offs) = match self.flipped {
+            false => (Direction::Clock6, [Direction::Clock8, Direction::Clock10]),
+            true => (Direction::Clock12, [Direction::Clock4, Direction::Clock2]),
+        };
+
+        let files = depth.file_range().map(|file| {
+            let ranks = depth.rank_range(file);
+            let rank = if self.flipped { ranks.hi() } else { ranks.lo() };
+            (
+                Cell::new_in(depth, rank, file),
+                file_off,
+                file.label().to_ascii_lowercase(),
+            )
+        });
+        let ranks = depth.rank_range(depth.centre().file).filter_map(|rank| {
+            let mut files = depth
+                .file_range()
+                .filter(|file| depth.contains(rank, *file));
+            let file = if self.flipped {
+                files.last()
+            } else {
+                files.next()
+            }?;
+            let cell = Cell::new_in(depth, rank, file);
+            let rank_off = rank_offs
+                .into_iter()
+                .find(|direction| self.board.next(cell, *direction).is_none())?;
+            Some((cell, rank_off, rank.label().to_string()))
+        });
+
+        files
+            .chain(ranks)
+            .map(|(cell, direction, label)| {
+                let hex = self.hexagon(cell, Color::default(), HighlightMode::default());
+                (hex.neighbour_center(direction), label)
+            })
+            .collect()
+    }
+}
+
+impl<'a> Widget for &'a BoardView<'a> {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer)
+    where
+        Self: Sized,
+    {
+        let (x_bounds, y_bounds) = self.bounds(area);
+
+        Canvas::default()
+            .x_bounds(x_bounds)
+            .y_bounds(y_bounds)
+            .block(Block::default().borders(Borders::ALL))
+            .marker(ratatui::symbols::Marker::Braille)
+            .background_color(TONE_CANVAS_BG)
+            .paint(|ctx| {
+                if self.coordinates {
+                    for (Delta { x, y }, label) in self.coordinate_labels() {
+                        ctx.print(x, y, Line::from(label).fg(Color::Gray));
+                    }
+                }
+
+                for hex in self.hexagons() {
+                    ctx.draw(&Tile {
+                        hex,
+                        occupant: self.board[hex.cell()].occupant(),
+                        hide_highlights: self.style.hide_highlights,
+                    });
+                }
+            })
+            .render(area, buf)
+    }
+}
+
+/// One cell as drawn: its hexagon and the mark of the piece on it.
+struct Tile<'a> {
+    hex: Hexagon,
+    occupant: Option<&'a dyn Piece>,
+    hide_highlights: bool,
+}
+
+impl Shape for Tile<'_> {
+    fn draw(&self, painter: &mut Painter) {
+        let Delta { x, y } = self.hex.center();
+
+        self.hex.draw_base(painter);
+
+        if !self.hide_highlights {
+            self.hex.draw_highlights(painter);
+        }
+
+        let mark_width = self.hex.len() / 2.;
+        let mark_height = self.hex.len() / 2.;
+
+        if let Some(piece) = self.occupant {
+            let mark_color = match piece.color() {
+                Player::Black => Color::Red,
+                Player::White => Color::White,
+            };
+
+            match piece.ty() {
+                PieceType::Queen => {
+                    Mark::<'Q'>::new(x, y, mark_width, mark_height, mark_color).draw(painter);
+                }
+                PieceType::King => {
+                    Mark::<'K'>::new(x, y, mark_width, mark_height, mark_color).draw(painter);
+                }
+                PieceType::Knight => {
+                    Mark::<'N'>::new(x, y, mark_width, mark_height, mark_color).draw(painter);
+                }
+                PieceType::Pawn => {
+                    Mark::<'P'>::new(x, y, mark_width, mark_height, mark_color).draw(painter);
+                }
+                PieceType::Bishop => {
+                    Mark::<'B'>::new(x, y, mark_width, mark_height, mark_color).draw(painter);
+                }
+                PieceType::Rook => {
+                    Mark::<'R'>::new(x, y, mark_width, mark_height, mark_color).draw(painter);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        board::Board,
+        unit::cell::Cell,
+        utils::{delta::Delta, file::File, fill_mode::FillMode, mode::Status, rank::Rank},
+    };
+
+    use super::{BoardStyle, BoardView};
+
+    const STYLE: BoardStyle = BoardStyle {
+        len: 1.,
+        padding: 0.,
+        fill_mode: FillMode::Wireframe,
+        hide_highlights: false,
+    };
+
+    fn view(board: &Board, flipped: bool) -> BoardView<'_> {
+        BoardView {
+            board,
+            style: STYLE,
+            flipped,
+            current: board.depth().centre(),
+            moves: &[],
+            div: 2.,
+            coordinates: true,
+        }
+    }
+
+    #[test]
+    fn points_map_back_to_the_nearest_cell() {
+        let board = Board::new();
+        let view = view(&board, false);
+        let f6 = Cell::new(Rank::Rank6, File::FileF);
+
+        assert_eq!(view.cell_under(Delta::new(0.1, -0.2)), Some(f6));
+        assert_eq!(
+            view.cell_under(Delta::new(0., 1.6)),
+            Some(Cell::new(Rank::Rank7, File::FileF))
+        );
+        assert_eq!(
+            view.cell_under(Delta::new(1.5, 0.9)),
+            Some(Cell::new(Rank::Rank6, File::FileG))
+        );
+        assert_eq!(view.cell_under(Delta::new(100., 0.)), None);
+    }
+
+    #[test]
+    fn terminal_positions_map_back_to_cells() {
+        let board = Board::new();
+        let view = view(&board, false);
+        let f6 = Cell::new(Rank::Rank6, File::FileF);
+
+        let area = ratatui::layout::Rect::new(0, 0, 82, 42);
+        assert_eq!(view.cell_at(area, 41, 21), Some(f6));
+        assert_eq!(view.cell_at(area, 1, 1), None);
+        assert_eq!(view.cell_at(area, 0, 21), None);
+    }
+
+    #[test]
+    fn flipped_boards_turn_about_the_centre() {
+        let board = Board::new();
+        let f7 = Cell::new(Rank::Rank7, File::FileF);
+
+        let flipped = view(&board, true);
+        let hex = flipped.hexagons().into_iter().find(|hex| hex.cell() == f7);
+        assert_eq!(hex.unwrap().center().y, -(3_f64.sqrt()));
+        assert_eq!(flipped.cell_under(Delta::new(0., -1.6)), Some(f7));
+        assert_eq!(
+            flipped.cell_under(Delta::new(1.5, 0.9)),
+            Some(Cell::new(Rank::Rank5, File::FileE))
+        );
+
+        assert_eq!(
+            view(&board, false).cell_under(Delta::new(0., 1.6)),
+            Some(f7)
+        );
+    }
+
+    #[test]
+    fn coordinates_sit_just_off_the_edge() {
+        let board = Board::new();
+        for flipped in [false, true] {
+            let view = view(&board, flipped);
+            let labels = view.coordinate_labels();
+            assert_eq!(labels.len(), 22);
+            for (center, label) in labels {
+                assert_eq!(view.cell_under(center), None, "{label} is on the board");
+            }
+        }
+
+        // Where each label is, in steps of the hex grid.
+        let at = |flipped: bool, label: &str| {
+            let labels = view(&board, flipped).coordinate_labels();
+            labels
+                .into_iter()
+                .find(|(_, other)| other == label)
+                .map(|(center, _)| {
+                    (
+                        (center.x / 1.5).round() as isize,
+                        (center.y / (3_f64.sqrt() / 2.)).round() as isize,
+                    )
+                })
+                .unwrap()
+        };
+
+        assert_eq!(at(false, "a"), (-5, -7));
+        assert_eq!(at(false, "f"), (0, -12));
+        assert_eq!(at(false, "1"), (-6, -6));
+        assert_eq!(at(false, "6"), (-6, 4));
+        assert_eq!(at(false, "11"), (-1, 11));
+
+        assert_eq!(at(true, "a"), (5, -7));
+        assert_eq!(at(true, "1"), (-6, 6));
+    }
+
+    #[test]
+    fn the_selected_piece_shows_its_moves() {
+        let mut board = Board::new();
+        let f5 = Cell::new(Rank::Rank5, File::FileF);
+        let moves = board.legal_moves(f5);
+        let mut view = view(&board, false);
+
+        // Black's pawn on f7 stops the double step.
+        view.moves = &moves;
+        assert_eq!(
+            view.statuses(),
+            [(Cell::new(Rank::Rank6, File::FileF), Status::Movable)]
+        );
+        view.moves = &[];
+        assert!(view.statuses().is_empty());
+    }
+}
@@ -0,0 +1,271 @@
+//! The command line of the `hexagon` binary.
+
+use anyhow::Result;
+use clap::{Parser, Subcommand, value_parser};
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use std::{path::PathBuf, time::Duration};
+
+use hexagon_core::{
+    App, Board, Bonus, Controller, Depth, FillMode, Keymap, Player, ScoringRule, TimeControl,
+    Variant,
+};
+
+#[derive(Subcommand)]
+enum Command {
+    Perft {
+        #[arg(value_name = "DEPTH")]
+        depth: u8,
+
+        #[arg(long, value_name = "FEN")]
+        fen: Option<String>,
+    },
+}
+
+#[derive(Parser)]
+#[command(version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(long, value_name = "LEN", default_value_t = 6.)]
+    len: f64,
+
+    #[arg(long, value_name = "PADDING", default_value_t = 0.8)]
+    padding: f64,
+
+    #[arg(
+        long,
+        value_name = "DEPTH",
+        value_parser = value_parser!(u8).range(1..=Depth::MAX as i64),
+        default_value_t = 6
+    )]
+    depth: u8,
+
+    #[arg(
+        long,
+        value_name = "COLOR_MODE",
+        value_enum,
+        default_value_t = FillMode::Wireframe
+    )]
+    color_mode: FillMode,
+
+    #[arg(long, value_name = "HIDE_PIECES")]
+    hide_pieces: bool,
+
+    #[arg(long, value_name = "HIDE_HIGHLIGHTS")]
+    hide_highlights: bool,
+
+    #[arg(long, value_name = "COORDINATES")]
+    coordinates: bool,
+
+    #[arg(long, value_name = "LOGGING")]
+    logging: bool,
+
+    #[arg(long, value_name = "FILE")]
+    load: Option<PathBuf>,
+
+    #[arg(long, value_name = "FILE")]
+    save: Option<PathBuf>,
+
+    #[arg(long, value_name = "CONTROLLER", value_enum, default_value_t = Controller::Human)]
+    white: Controller,
+
+    #[arg(long, value_name = "CONTROLLER", value_enum, default_value_t = Controller::Human)]
+    black: Controller,
+
+    /// How stalemates are scored; a loaded game keeps the rule it was saved with
+    /// unless this is given
+    #[arg(long, value_name = "RULE", value_enum)]
+    scoring: Option<ScoringRule>,
+
+    #[arg(long, value_name = "VARIANT", value_enum, default_value_t = Variant::Glinski)]
+    variant: Variant,
+
+    #[arg(long, value_name = "MINUTES")]
+    time: Option<f64>,
+
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        requires = "time",
+        conflicts_with = "delay"
+    )]
+    increment: Option<f64>,
+
+    #[arg(long, value_name = "SECONDS", requires = "time")]
+    delay: Option<f64>,
+
+    #[arg(long, value_name = "PLAYER", value_enum, default_value_t = Player::White)]
+    perspective: Player,
+
+    /// Turn the board to face whoever is to move
+    #[arg(long)]
+    auto_flip: bool,
+
+    /// Key bindings to use instead of $XDG_CONFIG_HOME/hexagon/keys.conf
+    ///
+    /// By default q w e / a s d and the arrow keys move around the board, Esc
+    /// or Ctrl+C quits and Ctrl+S saves. Quitting and saving used to be q and
+    /// s; a file with the lines `quit = q` and `save = s` brings them back.
+    /// Each line binds `action = key, key...`, replacing that action's
+    /// default keys.
+    #[arg(long, value_name = "FILE")]
+    keys: Option<PathBuf>,
+}
+
+impl Cli {
+    fn time_control(&self) -> Result<Option<TimeControl>> {
+        let Some(minutes) = self.time else {
+            return Ok(None);
+        };
+
+        let seconds = |secs: f64| {
+            Duration::try_from_secs_f64(secs)
+                .map_err(|_| anyhow::anyhow!("times cannot be negative; found: {secs}"))
+        };
+        let bonus = match (self.increment, self.delay) {
+            (Some(increment), _) => Bonus::Increment(seconds(increment)?),
+            (_, Some(delay)) => Bonus::Delay(seconds(delay)?),
+            (None, None) => Bonus::None,
+        };
+
+        Ok(Some(TimeControl {
+            base: seconds(minutes * 60.)?,
+            bonus,
+        }))
+    }
+
+    fn keymap(&self) -> Result<Keymap> {
+        if let Some(path) = &self.keys {
+            return Keymap::load(path);
+        }
+
+        let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+        match config_dir.map(|dir| dir.join("hexagon").join("keys.conf")) {
+            Some(path) if path.exists() => Keymap::load(&path),
+            _ => Ok(Keymap::default()),
+        }
+    }
+}
+
+fn setup_logger() -> Result<()> {
+    fern::Dispatch::new()
+        .format(|out, message, record| {
+            out.finish(format_args!(
+                "[{} {}] {}",
+                record.level(),
+                record.target(),
+                message
+            ))
+        })
+        .level(log::LevelFilter::Debug)
+        .chain(fern::log_file("output.log")?)
+        .apply()?;
+
+    Ok(())
+}
+
+fn perft(depth: u8, fen: Option<&str>, variant: Variant, board_depth: Depth) -> Result<()> {
+    let (mut board, player) = match fen {
+        Some(fen) => Board::from_fen(fen)?,
+        None => (Board::setup(variant, board_depth)?, Player::White),
+    };
+    board.set_variant(variant);
+
+    let mut divide = board.divide(player, depth);
+    divide.sort();
+
+    for (label, nodes) in &divide {
+        println!("{label}: {nodes}");
+    }
+    println!();
+    println!(
+        "Nodes searched: {}",
+        divide.iter().map(|(_, nodes)| nodes).sum::<u64>()
+    );
+
+    Ok(())
+}
+
+/// Parses the command line and runs the game or the requested subcommand.
+pub(crate) fn run() -> Result<()> {
+    let cli = Cli::parse();
+
+    let depth = Depth::new(cli.depth)?;
+
+    if let Some(Command::Perft { depth: plies, fen }) = cli.command {
+        return perft(plies, fen.as_deref(), cli.variant, depth);
+    }
+
+    if !cli.hide_pieces && !cli.variant.playable_on(depth) {
+        anyhow::bail!(
+            "the {} variant cannot be played with --depth {}; use --hide-pieces to preview the board",
+            cli.variant.label(),
+            cli.depth
+        );
+    }
+
+    let time_control = cli.time_control()?;
+    let keymap = cli.keymap()?;
+
+    if cli.logging {
+        setup_logger()?;
+    }
+
+    let app = if let Some(path) = cli.load {
+        App::load(
+            path,
+            cli.len,
+            cli.padding,
+            cli.color_mode,
+            cli.hide_highlights,
+        )?
+    } else if cli.hide_pieces {
+        App::preview(
+            cli.len,
+            cli.padding,
+            depth,
+            cli.color_mode,
+            cli.hide_highlights,
+        )
+    } else {
+        App::new(
+            cli.len,
+            cli.padding,
+            depth,
+            cli.color_mode,
+            cli.hide_highlights,
+            cli.variant,
+        )
+    };
+    let app = app
+        .with_controllers(cli.white, cli.black)
+        .with_scoring(cli.scoring)
+        .with_time_control(time_control)
+        .with_keymap(keymap)
+        .with_coordinates(cli.coordinates)
+        .with_perspective(cli.perspective, cli.auto_flip);
+    let mut app = match cli.save {
+        Some(path) => app.with_save_path(path),
+        None => app,
+    };
+
+    let mut terminal = ratatui::init();
+    let _guard = TerminalGuard;
+    crossterm::execute!(std::io::stdout(), EnableMouseCapture)?;
+    app.run(&mut terminal)
+}
+
+/// Hands the terminal back when dropped, so it is restored on every way out
+/// of [`run`], errors included.
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        // Nothing useful can be done if this fails while unwinding or exiting.
+        let _ = crossterm::execute!(std::io::stdout(), DisableMouseCapture);
+        ratatui::restore();
+    }
+}
@@ -0,0 +1,375 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    board::Board,
+    pieces::PieceType,
+    unit::cell::Cell,
+    utils::{moves::Move, player::Player, scoring::ScoringRule},
+};
+
+const MATE: i32 = 1_000_000;
+// Glinski's 3/4 point for a stalemate is halfway between a draw and a win.
+const STALEMATE: i32 = MATE / 2;
+const MOBILITY_WEIGHT: i32 = 4;
+
+// Glinski piece values: with three bishops covering every colour and twelve
+// knight jumps, knights edge out bishops while rooks lose a little to the
+// shorter files of the hexagon.
+fn piece_value(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::Pawn => 100,
+        PieceType::Knight => 340,
+        PieceType::Bishop => 310,
+        PieceType::Rook => 480,
+        PieceType::Queen => 950,
+        PieceType::King => 0,
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SearchLimits {
+    pub(crate) max_depth: u8,
+    pub(crate) time: Duration,
+}
+
+impl Default for SearchLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 4,
+            time: Duration::from_secs(2),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct BestMove {
+    pub(crate) src: Cell,
+    pub(crate) mov: Move,
+    pub(crate) score: i32,
+}
+
+struct Search<'a> {
+    deadline: Instant,
+    // Set from outside once the search's answer is no longer wanted.
+    stop: &'a AtomicBool,
+    scoring: ScoringRule,
+    aborted: bool,
+}
+
+fn evaluate(board: &Board, color: Player) -> i32 {
+    [color, color.toggle()]
+        .into_iter()
+        .zip([1, -1])
+        .map(|(side, sign)| {
+            let score = board
+                .occupied_cells(side)
+                .into_iter()
+                .filter_map(|cell| {
+                    let piece = board[cell].occupant()?;
+                    let mobility = board.pseudo_legal_moves(cell).len() as i32;
+
+                    Some(piece_value(piece.ty()) + mobility * MOBILITY_WEIGHT)
+                })
+                .sum::<i32>();
+
+            sign * score
+        })
+        .sum()
+}
+
+fn ordered_moves(board: &mut Board, color: Player) -> Vec<(Cell, Move)> {
+    let legal_moves = board
+        .occupied_cells(color)
+        .into_iter()
+        .flat_map(|src| {
+            board
+                .legal_moves(src)
+                .into_iter()
+                .map(move |mov| (src, mov))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut moves = legal_moves
+        .into_iter()
+        .map(|(src, mov)| {
+            let victim = board[mov.move_to]
+                .occupant()
+                .map_or(0, |piece| piece_value(piece.ty()));
+            let attacker = board[src]
+                .occupant()
+                .map_or(0, |piece| piece_value(piece.ty()));
+            let promotion = mov.move_type.promotion().map_or(0, piece_value);
+
+            (victim * 10 - attacker / 10 + promotion, (src, mov))
+        })
+        .collect::<Vec<_>>();
+
+    moves.sort_by_key(|(priority, _)| -priority);
+    moves.into_iter().map(|(_, mov)| mov).collect()
+}
+
+impl Search<'_> {
+    fn alpha_beta(
+        &mut self,
+        board: &mut Board,
+        color: Player,
+        depth: u8,
+        ply: i32,
+        mut alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        if Instant::now() >= self.deadline || self.stop.load(Ordering::Relaxed) {
+            self.aborted = true;
+            return 0;
+        }
+
+        if depth == 0 {
+            return evaluate(board, color);
+        }
+
+        let moves = ordered_moves(board, color);
+        if moves.is_empty() {
+            return match (board.is_in_check(color), self.scoring) {
+                (true, _) => -MATE + ply,
+                (false, ScoringRule::Glinski) => -STALEMATE + ply,
+                (false, ScoringRule::FideStyle) => 0,
+            };
+        }
+
+        for (src, mov) in moves {
+            let undo = board.make_move(src, mov);
+            let score = -self.alpha_beta(board, color.toggle(), depth - 1, ply + 1, -beta, -alpha);
+            board.unmake_move(undo);
+
+            if self.aborted {
+                return 0;
+            }
+
+            if score >= beta {
+                return beta;
+            }
+            alpha = alpha.max(score);
+        }
+
+        alpha
+    }
+
+    fn root(
+        &mut self,
+        board: &mut Board,
+        color: Player,
+        depth: u8,
+        moves: &[(Cell, Move)],
+    ) -> Option<BestMove> {
+        let mut best: Option<BestMove> = None;
+        let mut alpha = -MATE - 1;
+
+        for &(src, mov) in moves {
+            let undo = board.make_move(src, mov);
+            let score = -self.alpha_beta(board, color.toggle(), depth - 1, 1, -MATE - 1, -alpha);
+            board.unmake_move(undo);
+
+            if self.aborted {
+                return None;
+            }
+
+            if score > alpha {
+                alpha = score;
+                best = Some(BestMove { src, mov, score });
+            }
+        }
+
+        best
+    }
+}
+
+pub(crate) fn best_move(
+    board: &mut Board,
+    color: Player,
+    limits: SearchLimits,
+    scoring: ScoringRule,
+    stop: &AtomicBool,
+) -> Option<BestMove> {
+    let mut search = Search {
+        deadline: Instant::now() + limits.time,
+        stop,
+        scoring,
+        aborted: false,
+    };
+
+    let mut moves = ordered_moves(board, color);
+    let mut best = None;
+
+    for depth in 1..=limits.max_depth.max(1) {
+        let Some(found) = search.root(board, color, depth, &moves) else {
+            break;
+        };
+
+        // Search the previous iteration's best move first so that alpha-beta
+        // cuts as much of the next, deeper iteration as possible.
+        if let Some(idx) = moves
+            .iter()
+            .position(|(src, mov)| *src == found.src && mov.move_to == found.mov.move_to)
+        {
+            moves[..=idx].rotate_right(1);
+        }
+
+        best = Some(found);
+        if found.score.abs() >= MATE - i32::from(depth) {
+            break;
+        }
+    }
+
+    if stop.load(Ordering::Relaxed) {
+        return None;
+    }
+
+    best.or_else(|| {
+        moves
+            .first()
+            .map(|&(src, mov)| BestMove { src, mov, score: 0 })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::AtomicBool,
+        time::{Duration, Instant},
+    };
+
+    use crate::{
+        board::{Board, board_set},
+        unit::cell::Cell,
+        utils::{
+            file::File, notation::parse_san, player::Player, rank::Rank, scoring::ScoringRule,
+        },
+    };
+
+    use super::{STALEMATE, Search, SearchLimits, best_move, evaluate};
+
+    fn go() -> AtomicBool {
+        AtomicBool::new(false)
+    }
+
+    fn limits(max_depth: u8) -> SearchLimits {
+        SearchLimits {
+            max_depth,
+            time: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn starting_position_is_balanced() {
+        let board = Board::new();
+
+        assert_eq!(evaluate(&board, Player::White), 0);
+        assert_eq!(evaluate(&board, Player::Black), 0);
+    }
+
+    #[test]
+    fn finds_mate_in_one() {
+        let mut board = board_set!(
+            (Player::Black, king, King) on [Rank::Rank11, File::FileF],
+            (Player::White, king, King) on [Rank::Rank9, File::FileF],
+            (Player::White, queen, Queen) on [Rank::Rank1, File::FileB],
+        );
+
+        let best = best_move(
+            &mut board,
+            Player::White,
+            limits(2),
+            ScoringRule::Glinski,
+            &go(),
+        )
+        .unwrap();
+        assert_eq!(best.src, Cell::new(Rank::Rank1, File::FileB));
+        assert!(best.score > 0);
+
+        let _ = board.make_move(best.src, best.mov);
+        assert!(!board.has_legal_move(Player::Black) && board.is_in_check(Player::Black));
+    }
+
+    #[test]
+    fn takes_a_hanging_queen() {
+        let mut board = board_set!(
+            (Player::White, king, King) on [Rank::Rank1, File::FileA],
+            (Player::White, rook, Rook) on [Rank::Rank3, File::FileD],
+            (Player::Black, queen, Queen) on [Rank::Rank7, File::FileD],
+            (Player::Black, king, King) on [Rank::Rank7, File::FileK],
+        );
+
+        let best = best_move(
+            &mut board,
+            Player::White,
+            limits(2),
+            ScoringRule::Glinski,
+            &go(),
+        )
+        .unwrap();
+        assert_eq!(best.src, Cell::new(Rank::Rank3, File::FileD));
+        assert_eq!(best.mov.move_to, Cell::new(Rank::Rank7, File::FileD));
+    }
+
+    #[test]
+    fn returns_a_move_even_when_out_of_time() {
+        let mut board = Board::new();
+        let limits = SearchLimits {
+            max_depth: 4,
+            time: Duration::ZERO,
+        };
+
+        assert!(
+            best_move(
+                &mut board,
+                Player::White,
+                limits,
+                ScoringRule::Glinski,
+                &go()
+            )
+            .is_some()
+        );
+    }
+
+    #[test]
+    fn stalemate_is_scored_by_the_rule() {
+        let (mut board, _) = Board::from_fen("k5/2R4/2K5/9/10/11/10/9/8/7/6 w - 0 1").unwrap();
+        let (src, mov) = parse_san(&mut board, Player::White, "Rd5").unwrap();
+        let _ = board.make_move(src, mov);
+
+        let score = |board: &mut Board, scoring| {
+            let mut search = Search {
+                deadline: Instant::now() + Duration::from_secs(60),
+                stop: &go(),
+                scoring,
+                aborted: false,
+            };
+            search.alpha_beta(board, Player::Black, 1, 1, -STALEMATE * 2, STALEMATE * 2)
+        };
+
+        // Black has no move, which costs it 3/4 of a point under Glinski.
+        assert_eq!(score(&mut board, ScoringRule::Glinski), -STALEMATE + 1);
+        assert_eq!(score(&mut board, ScoringRule::FideStyle), 0);
+    }
+
+    #[test]
+    fn stopped_searches_give_up_without_a_move() {
+        let mut board = Board::new();
+        let stop = AtomicBool::new(true);
+
+        assert!(
+            best_move(
+                &mut board,
+                Player::White,
+                limits(4),
+                ScoringRule::Glinski,
+                &stop
+            )
+            .is_none()
+        );
+    }
+}
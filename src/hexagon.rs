@@ -1,17 +1,13 @@
-use ratatui::{
-    style::Color,
-    widgets::canvas::{Circle, Line, Painter, Shape},
-};
+use ratatui::style::Color;
+use ratatui::widgets::canvas::{Line, Painter, Shape};
 
 use crate::{
     unit::{UnitHexagon, cell::Cell},
     utils::{
         delta::Delta,
-        file::File,
+        direction::Direction,
         fill_mode::FillMode,
         mode::{HighlightMode, Status},
-        rank::Rank,
-        stack::Stack,
     },
 };
 
@@ -25,19 +21,19 @@ pub(crate) struct Hexagon {
     color: Color,
     fill_mode: FillMode,
     padding: f64,
+    flipped: bool,
 }
 
 impl Hexagon {
     pub(crate) fn try_new(
-        rank: Rank,
-        file: File,
+        cell: Cell,
         len: f64,
         padding: f64,
         color: Color,
         color_mode: FillMode,
         highlight_mode: HighlightMode,
     ) -> Result<Self> {
-        let unit = UnitHexagon::try_new(rank, file)?;
+        let unit = UnitHexagon::try_new(cell)?;
 
         Ok(Self {
             unit,
@@ -46,17 +42,10 @@ impl Hexagon {
             mode: highlight_mode,
             fill_mode: color_mode,
             padding,
+            flipped: false,
         })
     }
 
-    pub(crate) fn rank(&self) -> Rank {
-        self.cell().rank
-    }
-
-    pub(crate) fn file(&self) -> File {
-        self.cell().file
-    }
-
     pub(crate) fn cell(&self) -> Cell {
         self.unit.cell()
     }
@@ -65,28 +54,46 @@ impl Hexagon {
         self.len
     }
 
-    pub(crate) fn set_current(&mut self, current: bool) {
-        self.mode.set_current(current);
-    }
-
-    pub(crate) fn set_status(&mut self, status: Status) {
-        self.mode.set_status(status);
-    }
-
     pub(crate) fn new(
-        rank: Rank,
-        file: File,
+        cell: Cell,
         len: f64,
         padding: f64,
         color: Color,
         color_mode: FillMode,
         highlight_mode: HighlightMode,
     ) -> Self {
-        Self::try_new(rank, file, len, padding, color, color_mode, highlight_mode).unwrap()
+        Self::try_new(cell, len, padding, color, color_mode, highlight_mode).unwrap()
+    }
+
+    /// Turns the hexagon half way round the centre of the board, so that
+    /// the board is seen from Black's side.
+    pub(crate) fn set_flipped(&mut self, flipped: bool) {
+        self.flipped = flipped;
     }
 
     pub(crate) fn center(&self) -> Delta<f64> {
-        self.center_wrt(Self::default())
+        let center = self.center_wrt(Self::default());
+        if self.flipped { center * -1. } else { center }
+    }
+
+    /// Where the centre of the next cell in `direction` is drawn, even past
+    /// the edge of the board.
+    pub(crate) fn neighbour_center(&self, direction: Direction) -> Delta<f64> {
+        let step = self.unit.step(direction) * self.len;
+        self.center() + if self.flipped { step * -1. } else { step }
+    }
+
+    /// An unpadded, unfilled copy of this hexagon in `color`, for drawing
+    /// outlines over it.
+    fn outline(&self, len: f64, color: Color) -> Hexagon {
+        Hexagon {
+            len,
+            color,
+            padding: 1.,
+            fill_mode: FillMode::default(),
+            mode: HighlightMode::default(),
+            ..*self
+        }
     }
 
     pub(crate) fn center_wrt(&self, other: Self) -> Delta<f64> {
@@ -228,23 +235,6 @@ impl Hexagon {
             })
     }
 
-    fn contains_signed_area(&self, point: Delta<f64>) -> bool {
-        let points = [
-            self.point_e(),
-            self.point_se(),
-            self.point_sw(),
-            self.point_w(),
-            self.point_nw(),
-            self.point_ne(),
-        ];
-
-        points
-            .iter()
-            .zip(points.iter().cycle().skip(1))
-            .map(|(a, b)| (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x))
-            .all(|cross| cross <= 0.0)
-    }
-
     fn draw_filled(&self, painter: &mut Painter) {
         let (min_x, max_x) = (self.point_w().x, self.point_e().x);
         let (min_y, max_y) = (self.point_se().y, self.point_ne().y);
@@ -269,66 +259,21 @@ impl Hexagon {
         }
     }
 
-    fn draw_filled_alt(&self, painter: &mut Painter) {
-        let mut len = self.len;
-
-        const STEP: f64 = 0.1;
-
-        while len > 0. {
-            let hex = Hexagon::new(
-                self.rank(),
-                self.file(),
-                len,
-                1.,
-                self.color,
-                FillMode::default(),
-                HighlightMode::default(),
-            );
-
-            hex.draw_boundaries(painter);
-            len -= STEP;
-        }
-    }
-
     pub(crate) fn draw_highlights(&self, painter: &mut Painter) {
         match self.mode.current() {
             true => {
-                let hex = Hexagon::new(
-                    self.rank(),
-                    self.file(),
-                    self.len,
-                    1.,
-                    Color::White,
-                    FillMode::default(),
-                    HighlightMode::default(),
-                );
+                let hex = self.outline(self.len, Color::White);
 
                 hex.draw_boundaries(painter);
             }
             false => match self.mode.status() {
                 Status::Capturable => {
-                    let hex = Hexagon::new(
-                        self.rank(),
-                        self.file(),
-                        self.len,
-                        1.,
-                        Color::Red,
-                        FillMode::default(),
-                        HighlightMode::default(),
-                    );
+                    let hex = self.outline(self.len, Color::Red);
 
                     hex.draw_boundaries(painter);
                 }
                 Status::Movable => {
-                    let hex = Hexagon::new(
-                        self.rank(),
-                        self.file(),
-                        self.len,
-                        1.,
-                        Color::Blue,
-                        FillMode::default(),
-                        HighlightMode::default(),
-                    );
+                    let hex = self.outline(self.len, Color::Blue);
 
                     hex.draw_boundaries(painter);
                 }
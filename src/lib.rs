@@ -0,0 +1,49 @@
+//! Rules for hexagonal chess on boards of any size: Glinski's, McCooey's and
+//! Shafran's games, with legal move generation and FEN.
+//!
+//! The terminal game is behind the `tui` feature, which is on by default.
+//! Turn it off to use the rules without the interface:
+//!
+//! ```toml
+//! hexagon = { version = "0.1", default-features = false }
+//! ```
+
+#[cfg(feature = "tui")]
+mod app;
+mod board;
+#[cfg(feature = "tui")]
+mod engine;
+#[cfg(feature = "tui")]
+mod hexagon;
+mod pieces;
+#[cfg(feature = "tui")]
+mod state;
+mod unit;
+mod utils;
+
+pub use board::{Board, UndoToken};
+pub use pieces::{Piece, PieceType};
+pub use unit::cell::Cell;
+pub use utils::{
+    castling::Wing,
+    depth::Depth,
+    direction::Direction,
+    entry::Entry,
+    file::File,
+    moves::{GeneralMoveType, Move, MoveType, PawnMoveType},
+    player::Player,
+    rank::Rank,
+    variant::Variant,
+};
+
+// What the `hexagon` binary needs to set up and run the terminal game.
+#[cfg(feature = "tui")]
+pub use app::App;
+#[cfg(feature = "tui")]
+pub use utils::{
+    clock::{Bonus, TimeControl},
+    controller::Controller,
+    fill_mode::FillMode,
+    keymap::Keymap,
+    scoring::ScoringRule,
+};
@@ -1,96 +1,5 @@
-use app::App;
+mod cli;
 
-mod app;
-mod board;
-mod hexagon;
-mod pieces;
-mod state;
-mod unit;
-mod utils;
-
-use anyhow::Result;
-use clap::{Parser, value_parser};
-use utils::{depth::Depth, fill_mode::FillMode};
-
-#[derive(Parser)]
-#[command(version, about, long_about = None)]
-struct Cli {
-    #[arg(long, value_name = "LEN", default_value_t = 6.)]
-    len: f64,
-
-    #[arg(long, value_name = "PADDING", default_value_t = 0.8)]
-    padding: f64,
-
-    #[arg(
-        long,
-        value_name = "DEPTH",
-        value_parser = value_parser!(u8).range(1..=6),
-        default_value_t = 6
-    )]
-    depth: u8,
-
-    #[arg(
-        long,
-        value_name = "COLOR_MODE",
-        value_enum,
-        default_value_t = FillMode::Wireframe
-    )]
-    color_mode: FillMode,
-
-    #[arg(long, value_name = "HIDE_PIECES")]
-    hide_pieces: bool,
-
-    #[arg(long, value_name = "HIDE_HIGHLIGHTS")]
-    hide_highlights: bool,
-
-    #[arg(long, value_name = "LOGGING")]
-    logging: bool,
-}
-
-fn setup_logger() -> Result<()> {
-    fern::Dispatch::new()
-        .format(|out, message, record| {
-            out.finish(format_args!(
-                "[{} {}] {}",
-                record.level(),
-                record.target(),
-                message
-            ))
-        })
-        .level(log::LevelFilter::Debug)
-        .chain(fern::log_file("output.log")?)
-        .apply()?;
-
-    Ok(())
-}
-
-fn main() -> Result<()> {
-    let cli = Cli::parse();
-    let depth = Depth::new(cli.depth)?;
-
-    if !cli.hide_pieces && depth != Depth::default() {
-        anyhow::bail!(
-            "--depth is only for visual board/hexagon checks; use --hide-pieces with --depth < 6"
-        );
-    }
-
-    if cli.logging {
-        setup_logger()?;
-    }
-
-    let mut terminal = ratatui::init();
-    let mut app = if cli.hide_pieces {
-        App::preview(
-            cli.len,
-            cli.padding,
-            depth,
-            cli.color_mode,
-            cli.hide_highlights,
-        )
-    } else {
-        App::new(cli.len, cli.padding, cli.color_mode, cli.hide_highlights)
-    };
-    let res = app.run(&mut terminal);
-    ratatui::restore();
-    res
+fn main() -> anyhow::Result<()> {
+    cli::run()
 }
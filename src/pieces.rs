@@ -1,21 +1,20 @@
-use ratatui::{style::Color, widgets::canvas::Shape};
-
-use crate::{
-    board::Board,
-    pieces::{bishop::Bishop, king::King, knight::Knight, pawn::Pawn, queen::Queen, rook::Rook},
-    unit::cell::Cell,
-    utils::{mark::Mark, moves::Move},
-};
+use crate::utils::player::Player;
 
+#[cfg(all(test, feature = "tui"))]
 pub(crate) mod bishop;
+#[cfg(test)]
 pub(crate) mod king;
+#[cfg(all(test, feature = "tui"))]
 pub(crate) mod knight;
+#[cfg(test)]
 pub(crate) mod pawn;
+#[cfg(test)]
 pub(crate) mod queen;
+#[cfg(test)]
 pub(crate) mod rook;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub(crate) enum PieceType {
+pub enum PieceType {
     Bishop,
     King,
     Knight,
@@ -25,40 +24,62 @@ pub(crate) enum PieceType {
 }
 
 impl PieceType {
-    pub(crate) fn instantiate(self, color: Color) -> Box<dyn Piece> {
+    /// What a pawn may promote to, strongest first.
+    pub(crate) const PROMOTIONS: [Self; 4] = [Self::Queen, Self::Rook, Self::Bishop, Self::Knight];
+
+    pub fn label(self) -> &'static str {
         match self {
-            Self::Bishop => Box::new(Bishop::new(color)),
-            Self::King => Box::new(King::new(color)),
-            Self::Knight => Box::new(Knight::new(color)),
-            Self::Pawn => Box::new(Pawn::new(color)),
-            Self::Queen => Box::new(Queen::new(color)),
-            Self::Rook => Box::new(Rook::new(color)),
+            Self::Bishop => "B",
+            Self::King => "K",
+            Self::Knight => "N",
+            Self::Pawn => "P",
+            Self::Queen => "Q",
+            Self::Rook => "R",
         }
     }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        [
+            Self::Bishop,
+            Self::King,
+            Self::Knight,
+            Self::Pawn,
+            Self::Queen,
+            Self::Rook,
+        ]
+        .into_iter()
+        .find(|piece_type| piece_type.label() == label)
+    }
 }
 
-pub trait Piece {
-    fn color(&self) -> Color;
-    fn valid_moves(&self, board: &Board, current: Cell) -> Vec<Move>;
+pub trait Piece: Send {
+    fn color(&self) -> Player;
     fn ty(&self) -> PieceType;
 }
 
+// How a board stores a piece.
+impl Piece for (PieceType, Player) {
+    fn color(&self) -> Player {
+        self.1
+    }
+    fn ty(&self) -> PieceType {
+        self.0
+    }
+}
+
 #[cfg(test)]
 mod glinski_move_tests {
-    use ratatui::style::Color;
     use strum::IntoEnumIterator;
 
     use crate::{
         board::Board,
-        pieces::{
-            Piece, bishop::Bishop, king::King, knight::Knight, pawn::Pawn, queen::Queen, rook::Rook,
-        },
+        pieces::{PieceType, pawn::Pawn},
         unit::cell::Cell,
         utils::{
             depth::Depth,
             file::File,
-            fill_mode::FillMode,
             moves::{GeneralMoveType, Move, MoveType, PawnMoveType},
+            player::Player,
             rank::Rank,
         },
     };
@@ -96,7 +117,7 @@ mod glinski_move_tests {
     ];
 
     fn empty_board() -> Board {
-        Board::empty(0., 0., Depth::new(6).unwrap(), FillMode::Wireframe, false)
+        Board::empty(Depth::new(6).unwrap())
     }
 
     fn file_index(file: File) -> isize {
@@ -115,6 +136,7 @@ mod glinski_move_tests {
 
     fn all_cells() -> Vec<Cell> {
         File::iter()
+            .take_while(|file| *file <= File::FileL)
             .flat_map(|file| Rank::iter().filter_map(move |rank| Cell::try_new(rank, file).ok()))
             .collect()
     }
@@ -164,6 +186,13 @@ mod glinski_move_tests {
             .collect()
     }
 
+    fn moves_from(board: &mut Board, piece: PieceType, color: Player, cell: Cell) -> Vec<Move> {
+        board[cell].replace_occupant((piece, color));
+        let moves = board.pseudo_legal_moves(cell);
+        board[cell].remove_occupant();
+        moves
+    }
+
     fn destinations(moves: &[Move]) -> Vec<Cell> {
         moves.iter().map(|mov| mov.move_to).collect()
     }
@@ -176,12 +205,16 @@ mod glinski_move_tests {
 
     #[test]
     fn rook_empty_board_moves_match_glinski_oracle_from_every_cell() {
-        let board = empty_board();
-        let rook = Rook::new(Color::White);
+        let mut board = empty_board();
 
         for cell in all_cells() {
             assert_same_destinations(
-                destinations(&rook.valid_moves(&board, cell)),
+                destinations(&moves_from(
+                    &mut board,
+                    PieceType::Rook,
+                    Player::White,
+                    cell,
+                )),
                 expected_slider_moves(cell, &ROOK_DELTAS),
             );
         }
@@ -189,12 +222,16 @@ mod glinski_move_tests {
 
     #[test]
     fn bishop_empty_board_moves_match_glinski_oracle_from_every_cell() {
-        let board = empty_board();
-        let bishop = Bishop::new(Color::White);
+        let mut board = empty_board();
 
         for cell in all_cells() {
             assert_same_destinations(
-                destinations(&bishop.valid_moves(&board, cell)),
+                destinations(&moves_from(
+                    &mut board,
+                    PieceType::Bishop,
+                    Player::White,
+                    cell,
+                )),
                 expected_slider_moves(cell, &BISHOP_DELTAS),
             );
         }
@@ -202,14 +239,18 @@ mod glinski_move_tests {
 
     #[test]
     fn queen_empty_board_moves_match_glinski_oracle_from_every_cell() {
-        let board = empty_board();
-        let queen = Queen::new(Color::White);
+        let mut board = empty_board();
         let mut queen_deltas = Vec::from(ROOK_DELTAS);
         queen_deltas.extend(BISHOP_DELTAS);
 
         for cell in all_cells() {
             assert_same_destinations(
-                destinations(&queen.valid_moves(&board, cell)),
+                destinations(&moves_from(
+                    &mut board,
+                    PieceType::Queen,
+                    Player::White,
+                    cell,
+                )),
                 expected_slider_moves(cell, &queen_deltas),
             );
         }
@@ -217,12 +258,16 @@ mod glinski_move_tests {
 
     #[test]
     fn king_empty_board_moves_match_glinski_oracle_from_every_cell() {
-        let board = empty_board();
-        let king = King::new(Color::White);
+        let mut board = empty_board();
 
         for cell in all_cells() {
             assert_same_destinations(
-                destinations(&king.valid_moves(&board, cell)),
+                destinations(&moves_from(
+                    &mut board,
+                    PieceType::King,
+                    Player::White,
+                    cell,
+                )),
                 expected_leaper_moves(cell, &KING_DELTAS),
             );
         }
@@ -230,12 +275,16 @@ mod glinski_move_tests {
 
     #[test]
     fn knight_empty_board_moves_match_glinski_oracle_from_every_cell() {
-        let board = empty_board();
-        let knight = Knight::new(Color::White);
+        let mut board = empty_board();
 
         for cell in all_cells() {
             assert_same_destinations(
-                destinations(&knight.valid_moves(&board, cell)),
+                destinations(&moves_from(
+                    &mut board,
+                    PieceType::Knight,
+                    Player::White,
+                    cell,
+                )),
                 expected_leaper_moves(cell, &KNIGHT_DELTAS),
             );
         }
@@ -250,10 +299,10 @@ mod glinski_move_tests {
         let behind_enemy = Cell::new(Rank::Rank9, File::FileF);
 
         let mut board = empty_board();
-        board[ally].set_occupant(Pawn::new(Color::White));
-        board[enemy].set_occupant(Pawn::new(Color::Black));
+        board[ally].set_occupant(Pawn::new(Player::White));
+        board[enemy].set_occupant(Pawn::new(Player::Black));
 
-        let moves = Rook::new(Color::White).valid_moves(&board, start);
+        let moves = moves_from(&mut board, PieceType::Rook, Player::White, start);
 
         assert!(!destinations(&moves).contains(&ally));
         assert!(!destinations(&moves).contains(&behind_ally));
@@ -266,10 +315,14 @@ mod glinski_move_tests {
 
     #[test]
     fn white_pawn_empty_board_start_and_non_start_moves_match_glinski_rules() {
-        let board = empty_board();
+        let mut board = empty_board();
 
-        let moves =
-            Pawn::new(Color::White).valid_moves(&board, Cell::new(Rank::Rank5, File::FileF));
+        let moves = moves_from(
+            &mut board,
+            PieceType::Pawn,
+            Player::White,
+            Cell::new(Rank::Rank5, File::FileF),
+        );
         assert_same_destinations(
             destinations(&moves),
             vec![
@@ -278,8 +331,12 @@ mod glinski_move_tests {
             ],
         );
 
-        let moves =
-            Pawn::new(Color::White).valid_moves(&board, Cell::new(Rank::Rank6, File::FileF));
+        let moves = moves_from(
+            &mut board,
+            PieceType::Pawn,
+            Player::White,
+            Cell::new(Rank::Rank6, File::FileF),
+        );
         assert_same_destinations(
             destinations(&moves),
             vec![Cell::new(Rank::Rank7, File::FileF)],
@@ -288,10 +345,14 @@ mod glinski_move_tests {
 
     #[test]
     fn black_pawn_empty_board_start_and_non_start_moves_match_glinski_rules() {
-        let board = empty_board();
+        let mut board = empty_board();
 
-        let moves =
-            Pawn::new(Color::Black).valid_moves(&board, Cell::new(Rank::Rank7, File::FileF));
+        let moves = moves_from(
+            &mut board,
+            PieceType::Pawn,
+            Player::Black,
+            Cell::new(Rank::Rank7, File::FileF),
+        );
         assert_same_destinations(
             destinations(&moves),
             vec![
@@ -300,8 +361,12 @@ mod glinski_move_tests {
             ],
         );
 
-        let moves =
-            Pawn::new(Color::Black).valid_moves(&board, Cell::new(Rank::Rank6, File::FileF));
+        let moves = moves_from(
+            &mut board,
+            PieceType::Pawn,
+            Player::Black,
+            Cell::new(Rank::Rank6, File::FileF),
+        );
         assert_same_destinations(
             destinations(&moves),
             vec![Cell::new(Rank::Rank5, File::FileF)],
@@ -310,28 +375,41 @@ mod glinski_move_tests {
 
     #[test]
     fn pawn_capture_and_promotion_move_types_are_reported() {
-        let board = empty_board();
-        let moves =
-            Pawn::new(Color::White).valid_moves(&board, Cell::new(Rank::Rank9, File::FileE));
+        let mut board = empty_board();
+        let moves = moves_from(
+            &mut board,
+            PieceType::Pawn,
+            Player::White,
+            Cell::new(Rank::Rank9, File::FileE),
+        );
 
         assert!(moves.iter().any(|mov| {
             mov.move_to == Cell::new(Rank::Rank10, File::FileE)
                 && matches!(
                     mov.move_type,
-                    MoveType::Pawn(PawnMoveType::NonCapturePromotion)
+                    MoveType::Pawn(PawnMoveType::NonCapturePromotion { .. })
                 )
         }));
+        let choices = moves
+            .iter()
+            .filter_map(|mov| mov.move_type.promotion())
+            .collect::<Vec<_>>();
+        assert_eq!(choices, PieceType::PROMOTIONS);
 
         let mut board = empty_board();
-        board[Cell::new(Rank::Rank11, File::FileF)].set_occupant(Pawn::new(Color::Black));
-        let moves =
-            Pawn::new(Color::White).valid_moves(&board, Cell::new(Rank::Rank10, File::FileE));
+        board[Cell::new(Rank::Rank11, File::FileF)].set_occupant(Pawn::new(Player::Black));
+        let moves = moves_from(
+            &mut board,
+            PieceType::Pawn,
+            Player::White,
+            Cell::new(Rank::Rank10, File::FileE),
+        );
 
         assert!(moves.iter().any(|mov| {
             mov.move_to == Cell::new(Rank::Rank11, File::FileF)
                 && matches!(
                     mov.move_type,
-                    MoveType::Pawn(PawnMoveType::CapturePromotion)
+                    MoveType::Pawn(PawnMoveType::CapturePromotion { .. })
                 )
         }));
     }
@@ -1,86 +1,23 @@
-use ratatui::style::Color;
+use crate::utils::player::Player;
 
-use crate::{
-    unit::cell::Cell,
-    utils::{
-        direction::Direction,
-        file::File,
-        mark::Mark,
-        moves::{GeneralMoveType, MoveType},
-        rank::Rank,
-    },
-};
-
-use super::{Board, Move, Piece};
+use super::Piece;
 
 pub(crate) struct King {
-    color: Color,
+    color: Player,
 }
 
 impl King {
-    pub(crate) fn new(color: Color) -> Self {
+    pub(crate) fn new(color: Player) -> Self {
         Self { color }
     }
 }
 
-pub(crate) const WHITE_KING_STARTING_LOCATION: Cell =
-    unsafe { Cell::from_raw_parts(Rank::Rank1, File::FileG) };
-
-pub(crate) const BLACK_KING_STARTING_LOCATION: Cell =
-    unsafe { Cell::from_raw_parts(Rank::Rank10, File::FileG) };
-
 impl Piece for King {
     fn ty(&self) -> super::PieceType {
         super::PieceType::King
     }
 
-    fn color(&self) -> Color {
+    fn color(&self) -> Player {
         self.color
     }
-
-    fn valid_moves(&self, board: &Board, current: Cell) -> Vec<Move> {
-        const DIRECTIONS: [Direction; 12] = [
-            Direction::Clock1,
-            Direction::Clock2,
-            Direction::Clock3,
-            Direction::Clock4,
-            Direction::Clock5,
-            Direction::Clock6,
-            Direction::Clock7,
-            Direction::Clock8,
-            Direction::Clock9,
-            Direction::Clock10,
-            Direction::Clock11,
-            Direction::Clock12,
-        ];
-
-        let valid_moves = DIRECTIONS
-            .into_iter()
-            .filter_map(|direction| {
-                let Some(next_cell) = current.next(direction) else {
-                    return None;
-                };
-
-                match board[next_cell].occupant() {
-                    Some(piece) => {
-                        if piece.color() != self.color {
-                            Some(Move::new(
-                                next_cell,
-                                MoveType::Rest(GeneralMoveType::Capture),
-                            ))
-                        } else {
-                            None
-                        }
-                    }
-
-                    None => Some(Move::new(
-                        next_cell,
-                        MoveType::Rest(GeneralMoveType::NonCapture),
-                    )),
-                }
-            })
-            .collect::<Vec<_>>();
-
-        valid_moves
-    }
 }
@@ -1,101 +1,23 @@
-use ratatui::style::Color;
+use crate::utils::player::Player;
 
-use crate::{
-    unit::cell::Cell,
-    utils::{
-        direction::Direction,
-        file::File,
-        mark::Mark,
-        moves::{GeneralMoveType, MoveType},
-        rank::Rank,
-    },
-};
-
-use super::{Board, Move, Piece};
+use super::Piece;
 
 pub(crate) struct Knight {
-    color: Color,
+    color: Player,
 }
 
 impl Knight {
-    pub(crate) fn new(color: Color) -> Self {
+    pub(crate) fn new(color: Player) -> Self {
         Self { color }
     }
 }
 
-pub(crate) const WHITE_KNIGHT_STARTING_CELLS: [Cell; 2] = [
-    unsafe { Cell::from_raw_parts(Rank::Rank1, File::FileD) },
-    unsafe { Cell::from_raw_parts(Rank::Rank1, File::FileH) },
-];
-
-pub(crate) const BLACK_KNIGHT_STARTING_CELLS: [Cell; 2] = [
-    unsafe { Cell::from_raw_parts(Rank::Rank9, File::FileD) },
-    unsafe { Cell::from_raw_parts(Rank::Rank9, File::FileH) },
-];
-
 impl Piece for Knight {
     fn ty(&self) -> super::PieceType {
         super::PieceType::Knight
     }
 
-    fn color(&self) -> Color {
+    fn color(&self) -> Player {
         self.color
     }
-
-    fn valid_moves(&self, board: &Board, current: Cell) -> Vec<Move> {
-        const DIRECTIONS: [Direction; 6] = [
-            Direction::Clock2,
-            Direction::Clock4,
-            Direction::Clock6,
-            Direction::Clock8,
-            Direction::Clock10,
-            Direction::Clock12,
-        ];
-
-        let valid_moves = DIRECTIONS
-            .into_iter()
-            .flat_map(|direction| {
-                let Some((position_a, position_b)) = current
-                    .next(direction)
-                    .map(|next| next.next(direction))
-                    .flatten()
-                    .map(|cell| {
-                        (
-                            cell.next(direction.turn_clockwise()),
-                            cell.next(direction.turn_counter_clockwise()),
-                        )
-                    })
-                else {
-                    return vec![];
-                };
-
-                [position_a, position_b]
-                    .into_iter()
-                    .filter_map(|position| {
-                        position
-                            .map(|cell| match board[cell].occupant() {
-                                Some(piece) => {
-                                    if piece.color() != self.color {
-                                        Some(Move::new(
-                                            cell,
-                                            MoveType::Rest(GeneralMoveType::Capture),
-                                        ))
-                                    } else {
-                                        None
-                                    }
-                                }
-
-                                None => Some(Move::new(
-                                    cell,
-                                    MoveType::Rest(GeneralMoveType::NonCapture),
-                                )),
-                            })
-                            .flatten()
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .collect::<Vec<_>>();
-
-        valid_moves
-    }
 }
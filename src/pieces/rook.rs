@@ -1,35 +1,13 @@
-use ratatui::style::Color;
-
-use crate::{
-    board::Board,
-    unit::cell::Cell,
-    utils::{
-        direction::Direction,
-        file::File,
-        mark::Mark,
-        moves::{Move, single_direction_moves},
-        rank::Rank,
-    },
-};
+use crate::utils::player::Player;
 
 use super::Piece;
 
-pub(crate) const BLACK_ROOK_STARTING_CELLS: [Cell; 2] = [
-    unsafe { Cell::from_raw_parts(Rank::Rank8, File::FileC) },
-    unsafe { Cell::from_raw_parts(Rank::Rank8, File::FileI) },
-];
-
-pub(crate) const WHITE_ROOK_STARTING_CELLS: [Cell; 2] = [
-    unsafe { Cell::from_raw_parts(Rank::Rank1, File::FileC) },
-    unsafe { Cell::from_raw_parts(Rank::Rank1, File::FileI) },
-];
-
 pub(crate) struct Rook {
-    color: Color,
+    color: Player,
 }
 
 impl Rook {
-    pub(crate) fn new(color: Color) -> Self {
+    pub(crate) fn new(color: Player) -> Self {
         Self { color }
     }
 }
@@ -39,25 +17,7 @@ impl Piece for Rook {
         super::PieceType::Rook
     }
 
-    fn color(&self) -> Color {
+    fn color(&self) -> Player {
         self.color
     }
-
-    fn valid_moves(&self, board: &Board, current: Cell) -> Vec<Move> {
-        const ALLOWED_DIRECTIONS: [Direction; 6] = [
-            Direction::Clock2,
-            Direction::Clock4,
-            Direction::Clock6,
-            Direction::Clock8,
-            Direction::Clock10,
-            Direction::Clock12,
-        ];
-
-        let valid_moves = ALLOWED_DIRECTIONS
-            .into_iter()
-            .flat_map(|direction| single_direction_moves(current, self.color, direction, board))
-            .collect::<Vec<_>>();
-
-        valid_moves
-    }
 }
@@ -1,27 +1,31 @@
-use ratatui::{
-    layout::{Constraint, Layout},
-    style::Color,
-    widgets::{
-        Block, Borders, Widget,
-        canvas::{Canvas, Context},
-    },
-};
+use std::time::Duration;
+
+use anyhow::{Result, bail};
 
 use crate::{
-    board::{Board, EnPassant},
+    board::{Board, STARTING_FEN},
     pieces::PieceType,
     unit::cell::Cell,
     utils::{
-        consts::TONE_CANVAS_BG,
+        clock::{Clock, TimeControl},
         direction::Direction,
-        history::History,
-        mark::Mark,
-        moves::{GeneralMoveType, MoveType, PawnMoveType},
+        game_result::GameResult,
+        history::{History, MoveRecord},
+        moves::Move,
+        notation::parse_move,
         player::Player,
         progression::MoveProgression,
+        scoring::ScoringRule,
+        variant::Variant,
     },
 };
 
+mod save;
+#[cfg(feature = "tui")]
+mod view;
+
+const FIFTY_MOVE_HALFMOVES: u32 = 100;
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub(crate) enum GameStatus {
     #[default]
@@ -29,11 +33,35 @@ pub(crate) enum GameStatus {
     Check,
     Checkmate,
     Stalemate,
+    InsufficientMaterial,
+    ThreefoldRepetition,
+    FiftyMoveRule,
+    TimeForfeit,
 }
 
 impl GameStatus {
+    pub(crate) fn evaluate(board: &mut Board, color: Player) -> Self {
+        let in_check = board.is_in_check(color);
+        let has_legal_move = board.has_legal_move(color);
+
+        match (in_check, has_legal_move) {
+            (false, true) => Self::InProgress,
+            (true, true) => Self::Check,
+            (true, false) => Self::Checkmate,
+            (false, false) => Self::Stalemate,
+        }
+    }
+
     fn is_terminal(self) -> bool {
-        matches!(self, Self::Checkmate | Self::Stalemate)
+        matches!(
+            self,
+            Self::Checkmate
+                | Self::Stalemate
+                | Self::InsufficientMaterial
+                | Self::ThreefoldRepetition
+                | Self::FiftyMoveRule
+                | Self::TimeForfeit
+        )
     }
 
     fn label(self) -> Option<&'static str> {
@@ -42,17 +70,39 @@ impl GameStatus {
             Self::Check => Some("CHECK"),
             Self::Checkmate => Some("MATE"),
             Self::Stalemate => Some("STALE"),
+            Self::TimeForfeit => Some("TIME"),
+            Self::InsufficientMaterial | Self::ThreefoldRepetition | Self::FiftyMoveRule => {
+                Some("DRAW")
+            }
         }
     }
 
-    fn color(self) -> Color {
+    /// How a finished game ended, as written in a saved game's
+    /// `Termination` tag.
+    fn termination(self) -> Option<&'static str> {
         match self {
-            Self::InProgress => Color::LightYellow,
-            Self::Check => Color::LightYellow,
-            Self::Checkmate => Color::Red,
-            Self::Stalemate => Color::LightGreen,
+            Self::InProgress | Self::Check => None,
+            Self::Checkmate => Some("checkmate"),
+            Self::Stalemate => Some("stalemate"),
+            Self::InsufficientMaterial => Some("insufficient material"),
+            Self::ThreefoldRepetition => Some("threefold repetition"),
+            Self::FiftyMoveRule => Some("fifty-move rule"),
+            Self::TimeForfeit => Some("time forfeit"),
         }
     }
+
+    fn from_termination(label: &str) -> Option<Self> {
+        [
+            Self::Checkmate,
+            Self::Stalemate,
+            Self::InsufficientMaterial,
+            Self::ThreefoldRepetition,
+            Self::FiftyMoveRule,
+            Self::TimeForfeit,
+        ]
+        .into_iter()
+        .find(|status| status.termination() == Some(label))
+    }
 }
 
 enum MoveOutcome {
@@ -72,9 +122,18 @@ pub(crate) struct State {
     player: Player,
     current: Cell,
     move_progression: MoveProgression,
+    // The legal moves of the selected piece, worked out once on selection.
+    selected_moves: Vec<Move>,
     history: History,
     status: GameStatus,
     panel: Panel,
+    start_fen: String,
+    variant: Variant,
+    scoring: ScoringRule,
+    result: Option<GameResult>,
+    clock: Option<Clock>,
+    auto_flip: bool,
+    flipped: bool,
 }
 
 impl Default for State {
@@ -83,82 +142,23 @@ impl Default for State {
             player: Player::default(),
             current: Cell::default(),
             move_progression: MoveProgression::default(),
+            selected_moves: vec![],
             history: History::default(),
             status: GameStatus::default(),
             panel: Panel::Visible {
                 width_percentage: 25,
             },
+            start_fen: format!("{STARTING_FEN} w - 0 1"),
+            variant: Variant::default(),
+            scoring: ScoringRule::default(),
+            result: None,
+            clock: None,
+            auto_flip: false,
+            flipped: false,
         }
     }
 }
 
-fn draw_mark(
-    ctx: &mut Context<'_>,
-    mark: char,
-    x: f64,
-    y: f64,
-    width: f64,
-    height: f64,
-    color: Color,
-) {
-    match mark {
-        'A' => ctx.draw(&Mark::<'A'>::new(x, y, width, height, color)),
-        'B' => ctx.draw(&Mark::<'B'>::new(x, y, width, height, color)),
-        'C' => ctx.draw(&Mark::<'C'>::new(x, y, width, height, color)),
-        'E' => ctx.draw(&Mark::<'E'>::new(x, y, width, height, color)),
-        'H' => ctx.draw(&Mark::<'H'>::new(x, y, width, height, color)),
-        'I' => ctx.draw(&Mark::<'I'>::new(x, y, width, height, color)),
-        'K' => ctx.draw(&Mark::<'K'>::new(x, y, width, height, color)),
-        'L' => ctx.draw(&Mark::<'L'>::new(x, y, width, height, color)),
-        'M' => ctx.draw(&Mark::<'M'>::new(x, y, width, height, color)),
-        'N' => ctx.draw(&Mark::<'N'>::new(x, y, width, height, color)),
-        'O' => ctx.draw(&Mark::<'O'>::new(x, y, width, height, color)),
-        'R' => ctx.draw(&Mark::<'R'>::new(x, y, width, height, color)),
-        'S' => ctx.draw(&Mark::<'S'>::new(x, y, width, height, color)),
-        'T' => ctx.draw(&Mark::<'T'>::new(x, y, width, height, color)),
-        'U' => ctx.draw(&Mark::<'U'>::new(x, y, width, height, color)),
-        'W' => ctx.draw(&Mark::<'W'>::new(x, y, width, height, color)),
-        'Y' => ctx.draw(&Mark::<'Y'>::new(x, y, width, height, color)),
-        _ => {}
-    }
-}
-
-fn draw_word(
-    ctx: &mut Context<'_>,
-    word: &str,
-    x: f64,
-    y: f64,
-    width: f64,
-    height: f64,
-    color: Color,
-) {
-    let gap = width * 0.35;
-    let count = word.chars().count() as f64;
-    let total_width = count * width + (count - 1.).max(0.) * gap;
-    let start = x - total_width / 2. + width / 2.;
-
-    word.chars().enumerate().for_each(|(idx, mark)| {
-        draw_mark(
-            ctx,
-            mark,
-            start + idx as f64 * (width + gap),
-            y,
-            width,
-            height,
-            color,
-        );
-    });
-}
-
-fn mark_width_for(word: &str, max_width: f64, height: f64) -> f64 {
-    const GAP_FACTOR: f64 = 0.35;
-
-    let count = word.chars().count() as f64;
-    let fit_width = max_width / (count + (count - 1.).max(0.) * GAP_FACTOR);
-
-    (height * 0.75).min(fit_width * 0.9)
-}
-
 impl State {
     pub(crate) fn new() -> Self {
         Self {
@@ -167,6 +167,16 @@ impl State {
         }
     }
 
+    pub(crate) fn for_board(board: &Board) -> Self {
+        Self {
+            start_fen: board.to_fen(Player::White),
+            variant: board.variant(),
+            history: History::starting_at(board.fullmove_number(), Player::White),
+            current: board.depth().centre(),
+            ..Self::new()
+        }
+    }
+
     pub(crate) fn panel(&self) -> &Panel {
         &self.panel
     }
@@ -180,20 +190,27 @@ impl State {
         };
     }
 
-    pub(crate) fn set_current(&mut self, board: &mut Board, cell: Cell) {
-        let current_cell = self.current;
-
-        board[current_cell].hex_mut().set_current(false);
-        board[cell].hex_mut().set_current(true);
+    pub(crate) fn current(&self) -> Cell {
+        self.current
+    }
 
+    pub(crate) fn set_current(&mut self, cell: Cell) {
         self.current = cell;
     }
 
-    pub(crate) fn move_current(&mut self, board: &mut Board, direction: Direction) {
+    pub(crate) fn move_current(&mut self, board: &Board, direction: Direction) {
         let next = board.next(self.current, direction);
 
         if let Some(next) = next {
-            self.set_current(board, next)
+            self.set_current(next)
+        }
+    }
+
+    /// The moves of the piece that is picked up, shown on the board.
+    pub(crate) fn selected_moves(&self) -> &[Move] {
+        match self.move_progression {
+            MoveProgression::PossiblyMoving(_) => &self.selected_moves,
+            _ => &[],
         }
     }
 
@@ -208,18 +225,20 @@ impl State {
                     return;
                 };
 
-                if occupant.color() != self.player.color() {
+                if occupant.color() != self.player {
                     return;
                 }
 
-                board.show_valid_moves(self.current);
+                self.selected_moves = board.legal_moves(self.current);
                 self.move_progression = MoveProgression::PossiblyMoving(self.current);
             }
             MoveProgression::PossiblyMoving(cell) => {
-                board.hide_valid_moves(cell);
                 self.move_progression = match self.possibly_move(cell, self.current, board) {
-                    MoveOutcome::AwaitingPromotion { at } => MoveProgression::Promoting { at },
+                    MoveOutcome::AwaitingPromotion { at } => {
+                        MoveProgression::Promoting { from: cell, at }
+                    }
                     MoveOutcome::Moved => {
+                        self.history.clear_redo();
                         self.finish_turn(board);
                         MoveProgression::Navigation
                     }
@@ -230,141 +249,304 @@ impl State {
         }
     }
 
+    /// Selects the piece on `cell`, or moves the selected piece there.
+    /// Clicking another of one's own pieces switches the selection to it,
+    /// and pressing the selected piece again keeps it selected so it can be
+    /// dragged.
+    pub(crate) fn click(&mut self, board: &mut Board, cell: Cell) {
+        if self.move_progression == MoveProgression::PossiblyMoving(cell) {
+            self.set_current(cell);
+            return;
+        }
+
+        if matches!(self.move_progression, MoveProgression::PossiblyMoving(_))
+            && board[cell]
+                .occupant()
+                .is_some_and(|piece| piece.color() == self.player)
+        {
+            self.move_progression = MoveProgression::Navigation;
+        }
+
+        self.set_current(cell);
+        self.toggle_help_or_move(board);
+    }
+
+    /// Ends a drag that started on `src` by moving its piece to `cell`.
+    /// Releasing over the starting cell leaves it selected, as a click would.
+    pub(crate) fn drop(&mut self, board: &mut Board, src: Cell, cell: Cell) {
+        if src == cell
+            || !matches!(self.move_progression, MoveProgression::PossiblyMoving(selected) if selected == src)
+        {
+            return;
+        }
+
+        self.set_current(cell);
+        self.toggle_help_or_move(board);
+    }
+
+    pub(crate) fn player(&self) -> Player {
+        self.player
+    }
+
+    pub(crate) fn is_game_over(&self) -> bool {
+        self.status.is_terminal()
+    }
+
     pub(crate) fn is_promoting(&self) -> bool {
         matches!(self.move_progression, MoveProgression::Promoting { .. })
     }
 
     pub(crate) fn select_promotion(&mut self, board: &mut Board, piece_type: PieceType) {
-        let MoveProgression::Promoting { at } = self.move_progression else {
+        let MoveProgression::Promoting { from, at } = self.move_progression else {
             return;
         };
 
-        board[at].replace_occupant(piece_type.instantiate(self.player.color()));
-        self.finish_turn(board);
+        if let Some(mov) = self
+            .moves_to(board, from, at)
+            .into_iter()
+            .find(|mov| mov.move_type.promotion() == Some(piece_type))
+        {
+            self.commit_move(from, mov, board);
+            self.history.clear_redo();
+            self.finish_turn(board);
+        }
         self.move_progression = MoveProgression::Navigation;
     }
 
-    fn finish_turn(&mut self, board: &mut Board) {
+    pub(crate) fn undo(&mut self, board: &mut Board) {
+        // A pawn waiting to promote has not moved yet, so there is only the
+        // choice to drop.
+        let awaiting_promotion = self.is_promoting();
+        self.move_progression = MoveProgression::Navigation;
+        if awaiting_promotion {
+            return;
+        }
+
+        let Some(record) = self.history.undo() else {
+            return;
+        };
+
+        board.unmake_move(record.undo);
         self.player = self.player.toggle();
+        self.face_player();
         self.update_status(board);
     }
 
-    fn update_status(&mut self, board: &mut Board) {
-        let color = self.player.color();
-        let in_check = board.is_in_check(color);
-        let has_legal_move = board.has_legal_move(color);
+    pub(crate) fn redo(&mut self, board: &mut Board) {
+        if self.is_promoting() {
+            return;
+        }
+
+        self.move_progression = MoveProgression::Navigation;
 
-        self.status = match (in_check, has_legal_move) {
-            (false, true) => GameStatus::InProgress,
-            (true, true) => GameStatus::Check,
-            (true, false) => GameStatus::Checkmate,
-            (false, false) => GameStatus::Stalemate,
+        let Some(record) = self.history.redo() else {
+            return;
         };
+
+        // Redone moves were legal here when they were first played, so this
+        // only fails if the board was changed behind the history's back.
+        if let Err(err) = self.replay(board, record.src(), record.undo.mov) {
+            log::error!("Could not redo {}: {err:#}", record.label());
+            self.history.unredo(record);
+        }
     }
 
-    fn panel_status(&self) -> Option<(&'static str, Color)> {
-        if self.is_promoting() {
-            return Some(("PROMOTE", Color::LightYellow));
+    /// Plays a new move, which starts a line of its own: the moves undone
+    /// before it can no longer be redone.
+    pub(crate) fn play(&mut self, board: &mut Board, src: Cell, mov: Move) -> Result<()> {
+        self.replay(board, src, mov)?;
+        self.history.clear_redo();
+
+        Ok(())
+    }
+
+    /// Plays `mov` from `src`, promoting to the piece it names, and drops
+    /// any selection, whose moves it may have changed.
+    fn replay(&mut self, board: &mut Board, src: Cell, mov: Move) -> Result<()> {
+        let Some(mov) = self
+            .moves_to(board, src, mov.move_to)
+            .into_iter()
+            .find(|legal| legal.move_type.promotion() == mov.move_type.promotion())
+        else {
+            bail!(
+                "{} to {} is not a legal move for {:?}",
+                src.label(),
+                mov.move_to.label(),
+                self.player
+            );
+        };
+
+        self.move_progression = MoveProgression::Navigation;
+        self.commit_move(src, mov, board);
+        self.finish_turn(board);
+
+        Ok(())
+    }
+
+    /// Plays a move typed at the prompt, dropping any half-made selection.
+    pub(crate) fn enter_move(&mut self, board: &mut Board, text: &str) -> Result<()> {
+        if self.status.is_terminal() {
+            bail!("The game is over");
         }
 
-        self.status
-            .label()
-            .map(|label| (label, self.status.color()))
+        let (src, mov) = parse_move(board, self.player, text)?;
+        self.play(board, src, mov)?;
+        self.set_current(mov.move_to);
+
+        Ok(())
     }
 
-    fn possibly_move(&mut self, src: Cell, dest: Cell, board: &mut Board) -> MoveOutcome {
-        let (moved_piece_color, moved_piece_type) = {
-            let Some(src_occupant) = board[src].occupant() else {
-                return MoveOutcome::Rejected;
-            };
+    /// Turns the board to the side to move after every move, for two
+    /// players sharing one screen.
+    pub(crate) fn set_auto_flip(&mut self, auto_flip: bool) {
+        self.auto_flip = auto_flip;
+        self.face_player();
+    }
 
-            if src_occupant.color() != self.player.color() {
-                return MoveOutcome::Rejected;
-            }
+    pub(crate) fn is_flipped(&self) -> bool {
+        self.flipped
+    }
 
-            (src_occupant.color(), src_occupant.ty())
-        };
+    /// Draws the board from Black's side when `flipped`, White's otherwise.
+    pub(crate) fn set_flipped(&mut self, flipped: bool) {
+        self.flipped = flipped;
+    }
 
-        let valid_move = board
-            .legal_moves(src)
-            .into_iter()
-            .find(|mov| mov.move_to == dest);
-
-        if let Some(mov) = valid_move {
-            let new_en_passant = if moved_piece_type == PieceType::Pawn {
-                let forward_direction = match moved_piece_color {
-                    ratatui::style::Color::White => Direction::Clock12,
-                    ratatui::style::Color::Black => Direction::Clock6,
-                    _ => unreachable!(),
-                };
+    fn face_player(&mut self) {
+        if self.auto_flip {
+            self.flipped = self.player == Player::Black;
+        }
+    }
+
+    pub(crate) fn start_clock(&mut self, control: TimeControl) {
+        self.clock = Some(Clock::new(control));
+    }
+
+    /// Runs the clock of the player to move and ends the game if their flag
+    /// falls. A flag against a side that can no longer be mated is a draw.
+    pub(crate) fn tick(&mut self, board: &Board, elapsed: Duration) {
+        if self.status.is_terminal() {
+            return;
+        }
 
-                src.next(forward_direction).and_then(|passed_over| {
-                    passed_over
-                        .next(forward_direction)
-                        .and_then(|double_step_dest| {
-                            if double_step_dest == dest {
-                                Some(EnPassant {
-                                    captured_pawn: dest,
-                                    capture_move_to: passed_over,
-                                    pawn_color: moved_piece_color,
-                                })
-                            } else {
-                                None
-                            }
-                        })
-                })
+        let Some(clock) = self.clock.as_mut() else {
+            return;
+        };
+
+        clock.tick(self.player, elapsed);
+        if clock.is_flagged(self.player) {
+            self.status = if board.has_mating_material(self.player.toggle()) {
+                GameStatus::TimeForfeit
             } else {
-                None
+                GameStatus::InsufficientMaterial
             };
+            self.result = GameResult::new(self.status, self.player, self.scoring);
+        }
+    }
 
-            match mov.move_type {
-                MoveType::Rest(general_move_type) => match general_move_type {
-                    GeneralMoveType::NonCapture => {
-                        board.move_occupant(src, dest);
-                    }
-                    GeneralMoveType::Capture => {
-                        board.move_occupant(src, dest);
-                    }
-                },
-                MoveType::Pawn(pawn_move_type) => match pawn_move_type {
-                    PawnMoveType::NonCapture => {
-                        board.move_occupant(src, dest);
-                    }
-                    PawnMoveType::NormalCapture => {
-                        board.move_occupant(src, dest);
-                    }
-                    PawnMoveType::EnPassant { remove_piece_on } => {
-                        board.move_occupant(src, dest);
-                        board[remove_piece_on].remove_occupant();
-                    }
-                    PawnMoveType::NonCapturePromotion => {
-                        board.move_occupant(src, dest);
-                        board[dest]
-                            .replace_occupant(PieceType::Queen.instantiate(moved_piece_color));
-                    }
-                    PawnMoveType::CapturePromotion => {
-                        board.move_occupant(src, dest);
-                        board[dest]
-                            .replace_occupant(PieceType::Queen.instantiate(moved_piece_color));
-                    }
-                },
-            }
+    fn finish_turn(&mut self, board: &mut Board) {
+        if let Some(clock) = self.clock.as_mut() {
+            clock.press(self.player);
+        }
+        self.player = self.player.toggle();
+        self.face_player();
+        self.update_status(board);
 
-            board.clear_en_passant();
-            if let Some(en_passant) = new_en_passant {
-                board.set_en_passant(en_passant);
-            }
+        if let Some(record) = self.history.last_mut() {
+            record.status = self.status;
+        }
+    }
+
+    fn update_status(&mut self, board: &mut Board) {
+        self.status = match GameStatus::evaluate(board, self.player) {
+            GameStatus::Checkmate => GameStatus::Checkmate,
+            GameStatus::Stalemate => GameStatus::Stalemate,
+            _ if board.has_insufficient_material() => GameStatus::InsufficientMaterial,
+            _ if board.halfmove_clock() >= FIFTY_MOVE_HALFMOVES => GameStatus::FiftyMoveRule,
+            _ if self.repetitions(board) >= 3 => GameStatus::ThreefoldRepetition,
+            status => status,
+        };
+        self.result = GameResult::new(self.status, self.player, self.scoring);
+    }
+
+    pub(crate) fn scoring(&self) -> ScoringRule {
+        self.scoring
+    }
+
+    pub(crate) fn set_scoring(&mut self, scoring: ScoringRule) {
+        self.scoring = scoring;
+        self.result = GameResult::new(self.status, self.player, self.scoring);
+    }
+
+    fn repetitions(&self, board: &Board) -> usize {
+        let position = board.position_hash(self.player);
+
+        // Only positions since the last pawn move or capture can come back.
+        1 + self
+            .history
+            .records()
+            .iter()
+            .rev()
+            .take(board.halfmove_clock() as usize)
+            .filter(|record| record.position == position)
+            .count()
+    }
 
-            if mov.move_type.is_promotion() {
+    /// Scrolls the history back a line, as far as a list of `rows` rows
+    /// can go.
+    pub(crate) fn scroll_history_up(&mut self, rows: usize) {
+        self.history.scroll_up(rows);
+    }
+
+    pub(crate) fn scroll_history_down(&mut self) {
+        self.history.scroll_down();
+    }
+
+    /// The legal moves of the player to move from `src` to `dest`, one for
+    /// each piece a promoting pawn may become.
+    fn moves_to(&self, board: &mut Board, src: Cell, dest: Cell) -> Vec<Move> {
+        if board[src]
+            .occupant()
+            .is_none_or(|piece| piece.color() != self.player)
+        {
+            return vec![];
+        }
+
+        board
+            .legal_moves(src)
+            .into_iter()
+            .filter(|mov| mov.move_to == dest)
+            .collect()
+    }
+
+    fn possibly_move(&mut self, src: Cell, dest: Cell, board: &mut Board) -> MoveOutcome {
+        match self.moves_to(board, src, dest).first() {
+            Some(mov) if mov.move_type.is_promotion() => {
                 MoveOutcome::AwaitingPromotion { at: dest }
-            } else {
+            }
+            Some(mov) => {
+                self.commit_move(src, *mov, board);
                 MoveOutcome::Moved
             }
-        } else {
-            MoveOutcome::Rejected
+            None => MoveOutcome::Rejected,
         }
     }
 
+    fn commit_move(&mut self, src: Cell, mov: Move, board: &mut Board) {
+        let Some(piece) = board[src].occupant().map(|piece| piece.ty()) else {
+            return;
+        };
+        let position = board.position_hash(self.player);
+        let undo = board.make_move(src, mov);
+
+        self.history.push(MoveRecord {
+            piece,
+            status: GameStatus::InProgress,
+            undo,
+            position,
+        });
+    }
+
     // pub(crate) fn toggle_valid_moves(&mut self, board: &mut Board) {
     //     match self.displaying_valid_moves {
     //         Some(cell) => {
@@ -377,120 +559,23 @@ impl State {
     // }
 }
 
-impl Widget for &State {
-    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer)
-    where
-        Self: Sized,
-    {
-        let block = Block::default().borders(Borders::ALL);
-        let inner = block.inner(area);
-        block.render(area, buf);
-
-        let [player_area, status_area, history_area] = Layout::vertical([
-            Constraint::Percentage(20),
-            Constraint::Percentage(15),
-            Constraint::Percentage(65),
-        ])
-        .areas(inner);
-
-        let player_y_dim = player_area.height as f64;
-        let player_x_dim = player_area.width as f64;
-        let status_y_dim = status_area.height as f64;
-        let status_x_dim = status_area.width as f64;
-        let history_y_dim = history_area.height as f64;
-        let history_x_dim = history_area.width as f64;
-
-        let player = Canvas::default()
-            .block(Block::default().borders(Borders::ALL))
-            .background_color(TONE_CANVAS_BG)
-            .x_bounds([-player_x_dim / 2., player_x_dim / 2.])
-            .y_bounds([-player_y_dim / 2., player_y_dim / 2.])
-            .paint(|ctx| {
-                let heading_height = player_y_dim * 0.18;
-                let heading_width = mark_width_for("TURN", player_x_dim * 0.8, heading_height);
-                let player_mark_size = (player_y_dim * 0.38).min(player_x_dim * 0.45);
-
-                draw_word(
-                    ctx,
-                    "TURN",
-                    0.,
-                    player_y_dim * 0.24,
-                    heading_width,
-                    heading_height,
-                    Color::LightYellow,
-                );
-
-                match self.player {
-                    Player::White => ctx.draw(&Mark::<'W'>::new(
-                        0.,
-                        -player_y_dim * 0.16,
-                        player_mark_size,
-                        player_mark_size,
-                        Color::White,
-                    )),
-                    Player::Black => ctx.draw(&Mark::<'B'>::new(
-                        0.,
-                        -player_y_dim * 0.16,
-                        player_mark_size,
-                        player_mark_size,
-                        Color::Red,
-                    )),
-                }
-            });
-
-        let status = Canvas::default()
-            .block(Block::default().borders(Borders::ALL))
-            .background_color(TONE_CANVAS_BG)
-            .x_bounds([-status_x_dim / 2., status_x_dim / 2.])
-            .y_bounds([-status_y_dim / 2., status_y_dim / 2.])
-            .paint(|ctx| {
-                if let Some((label, color)) = self.panel_status() {
-                    let label_height = status_y_dim * 0.5;
-                    let label_width = mark_width_for(label, status_x_dim * 0.85, label_height);
-
-                    draw_word(ctx, label, 0., 0., label_width, label_height, color);
-                }
-            });
-
-        let history = Canvas::default()
-            .block(Block::default().borders(Borders::ALL))
-            .background_color(TONE_CANVAS_BG)
-            .x_bounds([-history_x_dim / 2., history_x_dim / 2.])
-            .y_bounds([-history_y_dim / 2., history_y_dim / 2.])
-            .paint(|ctx| {
-                let heading_height = history_y_dim * 0.08;
-                let heading_width = mark_width_for("HISTORY", history_x_dim * 0.85, heading_height);
-
-                draw_word(
-                    ctx,
-                    "HISTORY",
-                    0.,
-                    history_y_dim * 0.42,
-                    heading_width,
-                    heading_height,
-                    Color::LightYellow,
-                );
-            });
-
-        player.render(player_area, buf);
-        status.render(status_area, buf);
-        history.render(history_area, buf);
-    }
-}
-
 #[cfg(test)]
 mod tests {
-    use ratatui::style::Color;
+    use std::time::Duration;
 
     use crate::{
-        board::Board,
-        pieces::{PieceType, king::King, pawn::Pawn, rook::Rook},
+        board::{Board, STARTING_FEN, UndoToken, board_set},
+        pieces::{PieceType, king::King, pawn::Pawn, queen::Queen, rook::Rook},
         unit::cell::Cell,
         utils::{
+            castling::CastlingRights,
+            clock::{Bonus, TimeControl},
             depth::Depth,
+            direction::Direction,
             file::File,
-            fill_mode::FillMode,
-            moves::{MoveType, PawnMoveType},
+            history::MoveRecord,
+            moves::{GeneralMoveType, Move, MoveType, PawnMoveType},
+            notation::parse_san,
             player::Player,
             progression::MoveProgression,
             rank::Rank,
@@ -500,15 +585,15 @@ mod tests {
     use super::{GameStatus, MoveOutcome, State};
 
     fn empty_board() -> Board {
-        Board::empty(0., 0., Depth::new(6).unwrap(), FillMode::Wireframe, false)
+        Board::empty(Depth::new(6).unwrap())
     }
 
     #[test]
-    fn white_pawn_promotion_defaults_to_queen_and_awaits_selection() {
+    fn white_pawn_promotion_awaits_selection_before_moving() {
         let src = Cell::new(Rank::Rank9, File::FileE);
         let dest = Cell::new(Rank::Rank10, File::FileE);
         let mut board = empty_board();
-        board[src].set_occupant(Pawn::new(Color::White));
+        board[src].set_occupant(Pawn::new(Player::White));
         let mut state = State {
             player: Player::White,
             current: src,
@@ -521,10 +606,9 @@ mod tests {
             MoveOutcome::AwaitingPromotion { at } if at == dest
         ));
 
-        assert!(board[src].occupant().is_none());
-        let promoted = board[dest].occupant().expect("promoted piece should exist");
-        assert_eq!(promoted.ty(), PieceType::Queen);
-        assert_eq!(promoted.color(), Color::White);
+        assert_eq!(board[src].occupant().unwrap().ty(), PieceType::Pawn);
+        assert!(board[dest].occupant().is_none());
+        assert!(state.history.records().is_empty());
     }
 
     #[test]
@@ -532,7 +616,7 @@ mod tests {
         let src = Cell::new(Rank::Rank9, File::FileE);
         let dest = Cell::new(Rank::Rank10, File::FileE);
         let mut board = empty_board();
-        board[src].set_occupant(Pawn::new(Color::White));
+        board[src].set_occupant(Pawn::new(Player::White));
         let mut state = State {
             player: Player::White,
             current: src,
@@ -544,24 +628,24 @@ mod tests {
         else {
             panic!("expected promotion to await selection");
         };
-        state.move_progression = MoveProgression::Promoting { at };
+        state.move_progression = MoveProgression::Promoting { from: src, at };
 
         state.select_promotion(&mut board, PieceType::Knight);
 
         let promoted = board[dest].occupant().expect("promoted piece should exist");
         assert_eq!(promoted.ty(), PieceType::Knight);
-        assert_eq!(promoted.color(), Color::White);
+        assert_eq!(promoted.color(), Player::White);
         assert!(matches!(state.player, Player::Black));
         assert!(!state.is_promoting());
     }
 
     #[test]
-    fn white_pawn_capture_promotion_defaults_to_queen() {
+    fn white_pawn_capture_promotion_awaits_selection_before_capturing() {
         let src = Cell::new(Rank::Rank10, File::FileE);
         let dest = Cell::new(Rank::Rank11, File::FileF);
         let mut board = empty_board();
-        board[src].set_occupant(Pawn::new(Color::White));
-        board[dest].set_occupant(Pawn::new(Color::Black));
+        board[src].set_occupant(Pawn::new(Player::White));
+        board[dest].set_occupant(Pawn::new(Player::Black));
         let mut state = State {
             player: Player::White,
             current: src,
@@ -574,10 +658,11 @@ mod tests {
             MoveOutcome::AwaitingPromotion { at } if at == dest
         ));
 
-        assert!(board[src].occupant().is_none());
-        let promoted = board[dest].occupant().expect("promoted piece should exist");
-        assert_eq!(promoted.ty(), PieceType::Queen);
-        assert_eq!(promoted.color(), Color::White);
+        assert_eq!(board[src].occupant().unwrap().ty(), PieceType::Pawn);
+        let captured = board[dest]
+            .occupant()
+            .expect("captured piece should remain");
+        assert_eq!(captured.color(), Player::Black);
     }
 
     #[test]
@@ -587,8 +672,8 @@ mod tests {
         let black_src = Cell::new(Rank::Rank6, File::FileF);
         let black_dest = Cell::new(Rank::Rank5, File::FileE);
         let mut board = empty_board();
-        board[white_src].set_occupant(Pawn::new(Color::White));
-        board[black_src].set_occupant(Pawn::new(Color::Black));
+        board[white_src].set_occupant(Pawn::new(Player::White));
+        board[black_src].set_occupant(Pawn::new(Player::Black));
         let mut state = State {
             player: Player::White,
             current: white_src,
@@ -601,10 +686,7 @@ mod tests {
             MoveOutcome::Moved
         ));
 
-        let en_passant_moves = board[black_src]
-            .occupant()
-            .unwrap()
-            .valid_moves(&board, black_src);
+        let en_passant_moves = board.pseudo_legal_moves(black_src);
         assert!(en_passant_moves.iter().any(|mov| {
             mov.move_to == black_dest
                 && matches!(
@@ -626,7 +708,7 @@ mod tests {
             .occupant()
             .expect("capturing pawn should move");
         assert_eq!(capturing_pawn.ty(), PieceType::Pawn);
-        assert_eq!(capturing_pawn.color(), Color::Black);
+        assert_eq!(capturing_pawn.color(), Player::Black);
     }
 
     #[test]
@@ -638,9 +720,9 @@ mod tests {
         let black_other_dest = Cell::new(Rank::Rank6, File::FileK);
         let expired_en_passant_dest = Cell::new(Rank::Rank5, File::FileE);
         let mut board = empty_board();
-        board[white_src].set_occupant(Pawn::new(Color::White));
-        board[black_en_passant_src].set_occupant(Pawn::new(Color::Black));
-        board[black_other_src].set_occupant(Pawn::new(Color::Black));
+        board[white_src].set_occupant(Pawn::new(Player::White));
+        board[black_en_passant_src].set_occupant(Pawn::new(Player::Black));
+        board[black_other_src].set_occupant(Pawn::new(Player::Black));
         let mut state = State {
             player: Player::White,
             current: white_src,
@@ -659,10 +741,7 @@ mod tests {
             MoveOutcome::Moved
         ));
 
-        let moves_after_expiry = board[black_en_passant_src]
-            .occupant()
-            .unwrap()
-            .valid_moves(&board, black_en_passant_src);
+        let moves_after_expiry = board.pseudo_legal_moves(black_en_passant_src);
         assert!(!moves_after_expiry.iter().any(|mov| {
             mov.move_to == expired_en_passant_dest
                 && matches!(
@@ -678,8 +757,8 @@ mod tests {
         let white_king = Cell::new(Rank::Rank6, File::FileF);
         let black_rook = Cell::new(Rank::Rank9, File::FileF);
         let mut board = empty_board();
-        board[white_king].set_occupant(King::new(Color::White));
-        board[black_rook].set_occupant(Rook::new(Color::Black));
+        board[white_king].set_occupant(King::new(Player::White));
+        board[black_rook].set_occupant(Rook::new(Player::Black));
 
         let mut state = State {
             player: Player::White,
@@ -689,4 +768,606 @@ mod tests {
 
         assert_eq!(state.status, GameStatus::Check);
     }
+
+    #[test]
+    fn committed_moves_are_recorded_with_capture_promotion_and_status() {
+        let src = Cell::new(Rank::Rank10, File::FileE);
+        let dest = Cell::new(Rank::Rank11, File::FileF);
+        let mut board = empty_board();
+        board[src].set_occupant(Pawn::new(Player::White));
+        board[dest].set_occupant(Rook::new(Player::Black));
+        board[Cell::new(Rank::Rank1, File::FileF)].set_occupant(King::new(Player::Black));
+        let mut state = State {
+            player: Player::White,
+            current: src,
+            move_progression: MoveProgression::Navigation,
+            ..State::default()
+        };
+
+        let MoveOutcome::AwaitingPromotion { at } = state.possibly_move(src, dest, &mut board)
+        else {
+            panic!("expected promotion to await selection");
+        };
+        state.move_progression = MoveProgression::Promoting { from: src, at };
+        state.select_promotion(&mut board, PieceType::Rook);
+
+        let record = state.history.last_mut().expect("move should be recorded");
+        assert_eq!(record.src(), src);
+        assert_eq!(record.dest(), dest);
+        assert_eq!(record.piece, PieceType::Pawn);
+        assert_eq!(record.captured(), Some(PieceType::Rook));
+        assert_eq!(record.move_type().promotion(), Some(PieceType::Rook));
+        assert_eq!(record.status, GameStatus::Check);
+        assert_eq!(record.label(), "PE10xRF11=R+");
+    }
+
+    #[test]
+    fn en_passant_is_recorded_as_a_pawn_capture() {
+        let white_src = Cell::new(Rank::Rank4, File::FileE);
+        let white_dest = Cell::new(Rank::Rank6, File::FileE);
+        let black_src = Cell::new(Rank::Rank6, File::FileF);
+        let black_dest = Cell::new(Rank::Rank5, File::FileE);
+        let mut board = empty_board();
+        board[white_src].set_occupant(Pawn::new(Player::White));
+        board[black_src].set_occupant(Pawn::new(Player::Black));
+        let mut state = State {
+            player: Player::White,
+            ..State::default()
+        };
+
+        state.possibly_move(white_src, white_dest, &mut board);
+        state.finish_turn(&mut board);
+        state.possibly_move(black_src, black_dest, &mut board);
+        state.finish_turn(&mut board);
+
+        let record = state.history.last_mut().expect("move should be recorded");
+        assert_eq!(record.captured(), Some(PieceType::Pawn));
+        assert!(matches!(
+            record.move_type(),
+            MoveType::Pawn(PawnMoveType::EnPassant { remove_piece_on }) if remove_piece_on == white_dest
+        ));
+        assert_eq!(
+            state
+                .history
+                .lines()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["  1. PE4-E6  PF6xE5 EP"]
+        );
+    }
+
+    #[test]
+    fn history_scrolls_back_from_the_latest_moves() {
+        let mut state = State::default();
+        [Rank::Rank1, Rank::Rank2, Rank::Rank3, Rank::Rank4]
+            .into_iter()
+            .flat_map(|rank| [rank, rank])
+            .for_each(|rank| {
+                let src = Cell::new(rank, File::FileA);
+                let mov = Move::new(
+                    src.next(Direction::Clock12).unwrap(),
+                    MoveType::Rest(GeneralMoveType::NonCapture),
+                );
+                state.history.push(MoveRecord {
+                    piece: PieceType::King,
+                    status: GameStatus::InProgress,
+                    undo: UndoToken {
+                        src,
+                        mov,
+                        captured: None,
+                        en_passant: None,
+                        castling: CastlingRights::default(),
+                        halfmove_clock: 0,
+                    },
+                    position: 0,
+                });
+            });
+
+        assert_eq!(
+            state.history.visible_lines(2)[1].to_string(),
+            "  4. KA4-A5  KA4-A5"
+        );
+
+        state.scroll_history_up(2);
+        assert_eq!(
+            state.history.visible_lines(2)[1].to_string(),
+            "  3. KA3-A4  KA3-A4"
+        );
+
+        (0..3).for_each(|_| state.scroll_history_up(2));
+        assert_eq!(
+            state.history.visible_lines(2)[0].to_string(),
+            "  1. KA1-A2  KA1-A2"
+        );
+
+        state.scroll_history_down();
+        assert_eq!(
+            state.history.visible_lines(2)[0].to_string(),
+            "  2. KA2-A3  KA2-A3"
+        );
+
+        (0..2).for_each(|_| state.scroll_history_down());
+        assert_eq!(
+            state.history.visible_lines(2)[1].to_string(),
+            "  4. KA4-A5  KA4-A5"
+        );
+    }
+
+    #[test]
+    fn history_is_numbered_from_the_starting_position() {
+        let (_, state) = load(&format!(
+            "[FEN \"{STARTING_FEN} b - 0 20\"]\n\n20... f6 21. e5"
+        ));
+
+        assert_eq!(state.history.lines(), [" 20... PF7-F6", " 21. PE4-E5"]);
+
+        let (_, state) = load("1. e5 f6 2. Nc3");
+        assert_eq!(
+            state.history.lines(),
+            ["  1. PE4-E5  PF7-F6", "  2. ND1-C3"]
+        );
+    }
+
+    #[test]
+    fn undo_and_redo_restore_captures_en_passant_and_turn() {
+        let white_src = Cell::new(Rank::Rank4, File::FileE);
+        let white_dest = Cell::new(Rank::Rank6, File::FileE);
+        let black_src = Cell::new(Rank::Rank6, File::FileF);
+        let black_dest = Cell::new(Rank::Rank5, File::FileE);
+        let mut board = empty_board();
+        board[white_src].set_occupant(Pawn::new(Player::White));
+        board[black_src].set_occupant(Pawn::new(Player::Black));
+        let mut state = State {
+            player: Player::White,
+            ..State::default()
+        };
+
+        state.possibly_move(white_src, white_dest, &mut board);
+        state.finish_turn(&mut board);
+        state.possibly_move(black_src, black_dest, &mut board);
+        state.finish_turn(&mut board);
+        let after = board.to_fen(state.player);
+
+        state.undo(&mut board);
+        assert!(matches!(state.player, Player::Black));
+        assert!(board[white_dest].occupant().is_some());
+        assert!(board[black_src].occupant().is_some());
+        assert!(board[black_dest].occupant().is_none());
+        assert_eq!(
+            board.en_passant_capture(Player::Black, black_dest),
+            Some(white_dest)
+        );
+
+        state.undo(&mut board);
+        assert!(matches!(state.player, Player::White));
+        assert!(board[white_src].occupant().is_some());
+        assert!(board[white_dest].occupant().is_none());
+        assert_eq!(board.en_passant_capture(Player::Black, black_dest), None);
+
+        state.redo(&mut board);
+        state.redo(&mut board);
+        assert_eq!(board.to_fen(state.player), after);
+        assert!(matches!(state.player, Player::White));
+    }
+
+    #[test]
+    fn a_redo_that_cannot_be_replayed_stays_on_the_redo_line() {
+        let src = Cell::new(Rank::Rank1, File::FileB);
+        let dest = Cell::new(Rank::Rank1, File::FileA);
+        let mut board = empty_board();
+        board[src].set_occupant(Queen::new(Player::White));
+        let mut state = State {
+            player: Player::White,
+            ..State::default()
+        };
+
+        state.possibly_move(src, dest, &mut board);
+        state.finish_turn(&mut board);
+        state.undo(&mut board);
+
+        let queen = board[src].remove_occupant().unwrap();
+        state.redo(&mut board);
+        assert!(state.history.records().is_empty());
+        assert!(matches!(state.player, Player::White));
+
+        board[src].replace_occupant(queen);
+        state.redo(&mut board);
+        assert_eq!(state.history.records().len(), 1);
+        assert!(board[dest].occupant().is_some());
+    }
+
+    #[test]
+    fn played_moves_replace_the_redo_line() {
+        let mut board = Board::new();
+        let mut state = State::for_board(&board);
+        let cell = |label| Cell::from_label(label).unwrap();
+        let push = |to| Move::new(cell(to), MoveType::Pawn(PawnMoveType::NonCapture));
+
+        state.play(&mut board, cell("e4"), push("e5")).unwrap();
+        state.play(&mut board, cell("f7"), push("f6")).unwrap();
+        state.undo(&mut board);
+        state.undo(&mut board);
+
+        state.redo(&mut board);
+        state.play(&mut board, cell("c7"), push("c6")).unwrap();
+        assert_eq!(state.history.records().len(), 2);
+
+        state.redo(&mut board);
+        assert_eq!(state.history.records().len(), 2);
+        assert_eq!(state.player, Player::White);
+    }
+
+    #[test]
+    fn undo_leaves_a_terminal_checkmate() {
+        let src = Cell::new(Rank::Rank1, File::FileB);
+        let dest = Cell::new(Rank::Rank1, File::FileA);
+        let mut board = empty_board();
+        board[Cell::new(Rank::Rank11, File::FileF)].set_occupant(King::new(Player::Black));
+        board[Cell::new(Rank::Rank9, File::FileF)].set_occupant(King::new(Player::White));
+        board[src].set_occupant(Queen::new(Player::White));
+        let mut state = State {
+            player: Player::White,
+            ..State::default()
+        };
+
+        state.possibly_move(src, dest, &mut board);
+        state.finish_turn(&mut board);
+        assert_eq!(state.status, GameStatus::Checkmate);
+
+        state.undo(&mut board);
+        assert_eq!(state.status, GameStatus::InProgress);
+        assert!(matches!(state.player, Player::White));
+        assert!(board[src].occupant().is_some());
+
+        state.redo(&mut board);
+        assert_eq!(state.status, GameStatus::Checkmate);
+        assert!(matches!(state.player, Player::Black));
+    }
+
+    #[test]
+    fn undo_restores_the_pawn_and_redo_replays_the_promotion_choice() {
+        let src = Cell::new(Rank::Rank9, File::FileE);
+        let dest = Cell::new(Rank::Rank10, File::FileE);
+        let mut board = empty_board();
+        board[src].set_occupant(Pawn::new(Player::White));
+        let mut state = State {
+            player: Player::White,
+            ..State::default()
+        };
+
+        let MoveOutcome::AwaitingPromotion { at } = state.possibly_move(src, dest, &mut board)
+        else {
+            panic!("expected promotion to await selection");
+        };
+        state.move_progression = MoveProgression::Promoting { from: src, at };
+
+        state.undo(&mut board);
+        assert!(!state.is_promoting());
+        assert!(matches!(state.player, Player::White));
+        assert_eq!(board[src].occupant().unwrap().ty(), PieceType::Pawn);
+        assert!(board[dest].occupant().is_none());
+
+        state.redo(&mut board);
+        assert!(!state.is_promoting());
+        assert!(matches!(state.player, Player::White));
+
+        state.move_progression = MoveProgression::Promoting { from: src, at };
+        state.select_promotion(&mut board, PieceType::Knight);
+
+        state.undo(&mut board);
+        assert!(matches!(state.player, Player::White));
+        assert_eq!(board[src].occupant().unwrap().ty(), PieceType::Pawn);
+
+        state.redo(&mut board);
+        assert!(!state.is_promoting());
+        assert!(matches!(state.player, Player::Black));
+        assert_eq!(board[dest].occupant().unwrap().ty(), PieceType::Knight);
+    }
+
+    fn load(text: &str) -> (Board, State) {
+        State::from_game_text(text).unwrap()
+    }
+
+    #[test]
+    fn third_occurrence_of_a_position_is_a_draw() {
+        let (mut board, mut state) = load("1. Nc3 Nc6 2. Nd1 Nd9 3. Nc3 Nc6 4. Nd1");
+        assert_eq!(state.status, GameStatus::InProgress);
+
+        let (src, mov) = parse_san(&mut board, Player::Black, "Nd9").unwrap();
+        state.play(&mut board, src, mov).unwrap();
+        assert_eq!(state.status, GameStatus::ThreefoldRepetition);
+        assert!(state.is_game_over());
+
+        state.undo(&mut board);
+        assert_eq!(state.status, GameStatus::InProgress);
+    }
+
+    #[test]
+    fn fifty_moves_without_pawn_move_or_capture_is_a_draw() {
+        let fen = "6/7/8/9/8P1/5K5/10/9/8/7/5k w - 99 80";
+
+        let (_, state) = load(&format!("[FEN \"{fen}\"]\n\nKf7"));
+        assert_eq!(state.status, GameStatus::FiftyMoveRule);
+
+        let (_, state) = load(&format!("[FEN \"{fen}\"]\n\ne10=Q"));
+        assert_eq!(state.status, GameStatus::InProgress);
+    }
+
+    fn status_of(mut board: Board) -> GameStatus {
+        let mut state = State {
+            player: Player::White,
+            ..State::default()
+        };
+        state.update_status(&mut board);
+
+        state.status
+    }
+
+    #[test]
+    fn bare_kings_and_a_bishop_without_corners_are_dead() {
+        let bare_kings = board_set!(
+            (Player::White, king, King) on [Rank::Rank1, File::FileA],
+            (Player::Black, king, King) on [Rank::Rank7, File::FileK],
+        );
+        assert_eq!(status_of(bare_kings), GameStatus::InsufficientMaterial);
+
+        // F3's shade has none of the six corners.
+        let cornerless = board_set!(
+            (Player::White, king, King) on [Rank::Rank1, File::FileA],
+            (Player::White, bishop, Bishop) on [Rank::Rank3, File::FileF],
+            (Player::Black, king, King) on [Rank::Rank7, File::FileK],
+        );
+        assert!(!cornerless.has_mating_material(Player::White));
+        assert_eq!(status_of(cornerless), GameStatus::InsufficientMaterial);
+
+        let bishop = board_set!(
+            (Player::White, king, King) on [Rank::Rank1, File::FileA],
+            (Player::White, bishop, Bishop) on [Rank::Rank2, File::FileF],
+            (Player::Black, king, King) on [Rank::Rank7, File::FileK],
+        );
+        assert!(bishop.has_mating_material(Player::White));
+        assert_eq!(status_of(bishop), GameStatus::InProgress);
+
+        let knight = board_set!(
+            (Player::White, king, King) on [Rank::Rank1, File::FileA],
+            (Player::Black, knight, Knight) on [Rank::Rank9, File::FileD],
+            (Player::Black, king, King) on [Rank::Rank7, File::FileK],
+        );
+        assert!(knight.has_mating_material(Player::Black));
+        assert_eq!(status_of(knight), GameStatus::InProgress);
+
+        // The defender's own pieces may block their king in, so only a bare
+        // king is safe from the bishop.
+        let defended = board_set!(
+            (Player::White, king, King) on [Rank::Rank1, File::FileA],
+            (Player::White, bishop, Bishop) on [Rank::Rank3, File::FileF],
+            (Player::Black, bishop, Bishop) on [Rank::Rank10, File::FileF],
+            (Player::Black, king, King) on [Rank::Rank7, File::FileK],
+        );
+        assert!(defended.has_mating_material(Player::White));
+        assert_eq!(status_of(defended), GameStatus::InProgress);
+    }
+
+    #[test]
+    fn lone_minor_pieces_mate_in_the_corners() {
+        let knight = board_set!(
+            (Player::White, king, King) on [Rank::Rank3, File::FileC],
+            (Player::White, knight, Knight) on [Rank::Rank4, File::FileB],
+            (Player::Black, king, King) on [Rank::Rank1, File::FileA],
+        );
+        let bishop = board_set!(
+            (Player::White, king, King) on [Rank::Rank3, File::FileC],
+            (Player::White, bishop, Bishop) on [Rank::Rank3, File::FileB],
+            (Player::Black, king, King) on [Rank::Rank1, File::FileA],
+        );
+        let same_shade = board_set!(
+            (Player::White, king, King) on [Rank::Rank3, File::FileC],
+            (Player::White, bishop, Bishop) on [Rank::Rank3, File::FileB],
+            (Player::Black, bishop, Bishop) on [Rank::Rank6, File::FileB],
+            (Player::Black, king, King) on [Rank::Rank1, File::FileA],
+        );
+
+        for mut board in [knight, bishop, same_shade] {
+            assert_eq!(
+                GameStatus::evaluate(&mut board, Player::Black),
+                GameStatus::Checkmate
+            );
+        }
+    }
+
+    #[test]
+    fn mating_material_keeps_the_game_going() {
+        let rook = board_set!(
+            (Player::White, king, King) on [Rank::Rank1, File::FileA],
+            (Player::White, rook, Rook) on [Rank::Rank3, File::FileD],
+            (Player::Black, king, King) on [Rank::Rank7, File::FileK],
+        );
+        assert_eq!(status_of(rook), GameStatus::InProgress);
+
+        let pawn = board_set!(
+            (Player::White, king, King) on [Rank::Rank1, File::FileA],
+            (Player::Black, pawn, Pawn) on [Rank::Rank7, File::FileF],
+            (Player::Black, king, King) on [Rank::Rank7, File::FileK],
+        );
+        assert_eq!(status_of(pawn), GameStatus::InProgress);
+
+        let two_knights = board_set!(
+            (Player::White, king, King) on [Rank::Rank1, File::FileA],
+            (Player::White, knight, Knight) on [Rank::Rank1, File::FileD],
+            (Player::White, knight, Knight) on [Rank::Rank1, File::FileH],
+            (Player::Black, king, King) on [Rank::Rank7, File::FileK],
+        );
+        assert_eq!(status_of(two_knights), GameStatus::InProgress);
+    }
+
+    fn clocked(fen: &str, bonus: Bonus) -> (Board, State) {
+        let (board, mut state) = load(&format!("[FEN \"{fen}\"]\n"));
+        state.start_clock(TimeControl {
+            base: Duration::from_secs(60),
+            bonus,
+        });
+
+        (board, state)
+    }
+
+    #[test]
+    fn clicks_select_reselect_and_move() {
+        let mut board = Board::new();
+        let mut state = State::for_board(&board);
+        let cell = |label| Cell::from_label(label).unwrap();
+
+        state.click(&mut board, cell("d3"));
+        assert_eq!(
+            state.move_progression,
+            MoveProgression::PossiblyMoving(cell("d3"))
+        );
+
+        state.click(&mut board, cell("e4"));
+        assert_eq!(
+            state.move_progression,
+            MoveProgression::PossiblyMoving(cell("e4"))
+        );
+
+        state.click(&mut board, cell("e5"));
+        assert_eq!(state.move_progression, MoveProgression::Navigation);
+        assert_eq!(state.player, Player::Black);
+        assert!(board[cell("e5")].occupant().is_some());
+        assert_eq!(state.current, cell("e5"));
+    }
+
+    #[test]
+    fn dragging_a_piece_moves_it_on_release() {
+        let mut board = Board::new();
+        let mut state = State::for_board(&board);
+        let cell = |label| Cell::from_label(label).unwrap();
+
+        // Releasing where the drag began keeps the piece selected.
+        state.click(&mut board, cell("e4"));
+        state.drop(&mut board, cell("e4"), cell("e4"));
+        assert_eq!(
+            state.move_progression,
+            MoveProgression::PossiblyMoving(cell("e4"))
+        );
+
+        // So does pressing it again to start the drag.
+        state.click(&mut board, cell("e4"));
+        assert_eq!(
+            state.move_progression,
+            MoveProgression::PossiblyMoving(cell("e4"))
+        );
+
+        state.drop(&mut board, cell("e4"), cell("e6"));
+        assert_eq!(state.player, Player::Black);
+        assert!(board[cell("e4")].occupant().is_none());
+        assert!(board[cell("e6")].occupant().is_some());
+
+        // A drag from an empty cell selects nothing and so drops nothing.
+        state.click(&mut board, cell("f6"));
+        state.drop(&mut board, cell("f6"), cell("f5"));
+        assert_eq!(state.move_progression, MoveProgression::Navigation);
+        assert_eq!(state.player, Player::Black);
+    }
+
+    #[test]
+    fn typed_moves_replace_the_selection_and_the_redo_line() {
+        let mut board = Board::new();
+        let mut state = State::for_board(&board);
+        let cell = |label| Cell::from_label(label).unwrap();
+
+        state.enter_move(&mut board, "e4-e5").unwrap();
+        state.undo(&mut board);
+        state.click(&mut board, cell("d3"));
+
+        state.enter_move(&mut board, "f6").unwrap();
+        assert_eq!(state.move_progression, MoveProgression::Navigation);
+        assert_eq!(state.current, cell("f6"));
+        assert_eq!(state.player, Player::Black);
+
+        state.redo(&mut board);
+        assert_eq!(state.player, Player::Black);
+        assert!(state.enter_move(&mut board, "f6").is_err());
+    }
+
+    #[test]
+    fn typed_non_ascii_moves_are_errors() {
+        let mut board = Board::new();
+        let mut state = State::for_board(&board);
+
+        for text in ["é5", "Né5", "f5-é6"] {
+            assert!(state.enter_move(&mut board, text).is_err(), "{text}");
+        }
+        assert_eq!(state.player, Player::White);
+    }
+
+    #[test]
+    fn auto_flip_turns_the_board_to_the_side_to_move() {
+        let mut board = Board::new();
+        let mut state = State::for_board(&board);
+
+        state.enter_move(&mut board, "f6").unwrap();
+        assert!(!state.is_flipped());
+
+        state.set_auto_flip(true);
+        assert!(state.is_flipped());
+        state.enter_move(&mut board, "e6").unwrap();
+        assert!(!state.is_flipped());
+        state.undo(&mut board);
+        assert!(state.is_flipped());
+        state.redo(&mut board);
+        assert!(!state.is_flipped());
+    }
+
+    #[test]
+    fn moves_press_the_clock_and_add_the_bonus() {
+        let (mut board, mut state) = clocked(
+            "6/7/8/9/10/5K4R/10/9/8/7/5k w - 0 1",
+            Bonus::Increment(Duration::from_secs(5)),
+        );
+
+        state.tick(&board, Duration::from_secs(2));
+        let (src, mov) = parse_san(&mut board, Player::White, "Kf7").unwrap();
+        state.play(&mut board, src, mov).unwrap();
+        state.tick(&board, Duration::from_secs(10));
+
+        let clock = state.clock.unwrap();
+        assert_eq!(clock.remaining(Player::White), Duration::from_secs(63));
+        assert_eq!(clock.remaining(Player::Black), Duration::from_secs(50));
+    }
+
+    #[test]
+    fn running_out_of_time_loses_unless_the_opponent_cannot_mate() {
+        let (board, mut state) = clocked("6/7/8/9/10/5K4R/10/9/8/7/5k b - 0 1", Bonus::None);
+        state.tick(&board, Duration::from_secs(61));
+
+        assert_eq!(state.status, GameStatus::TimeForfeit);
+        assert!(state.is_game_over());
+        assert_eq!(state.result.unwrap().winner, Some(Player::White));
+
+        // A lone knight can still mate, so it wins on time too.
+        let (board, mut state) = clocked("6/7/8/9/10/5K4N/10/9/8/7/5k b - 0 1", Bonus::None);
+        state.tick(&board, Duration::from_secs(61));
+        assert_eq!(state.status, GameStatus::TimeForfeit);
+
+        let (board, mut state) = clocked("6/7/8/9/10/5K4R/10/9/8/7/5k w - 0 1", Bonus::None);
+        state.tick(&board, Duration::from_secs(61));
+
+        assert_eq!(state.status, GameStatus::InsufficientMaterial);
+        assert_eq!(state.result.unwrap().winner, None);
+    }
+
+    #[test]
+    fn games_lost_on_time_reload_as_finished() {
+        let (board, mut state) = clocked("6/7/8/9/10/5K4R/10/9/8/7/5k b - 0 1", Bonus::None);
+        state.tick(&board, Duration::from_secs(61));
+
+        let saved = state.to_game_text().unwrap();
+        assert!(saved.contains("[Result \"1-0\"]"), "{saved}");
+        assert!(saved.contains("[Termination \"time forfeit\"]"), "{saved}");
+
+        let (_, reloaded) = load(&saved);
+        assert_eq!(reloaded.status, GameStatus::TimeForfeit);
+        assert!(reloaded.is_game_over());
+        assert_eq!(reloaded.result, state.result);
+    }
 }
@@ -0,0 +1,299 @@
+use anyhow::{Context, Result, anyhow};
+
+use crate::{
+    board::Board,
+    utils::{
+        depth::Depth,
+        game_result::{GameResult, Score},
+        history::History,
+        notation::{parse_san, to_san},
+        player::Player,
+        scoring::ScoringRule,
+        variant::Variant,
+    },
+};
+
+use super::{GameStatus, State};
+
+const MOVES_PER_LINE: usize = 8;
+
+fn tag<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    line.trim()
+        .strip_prefix('[')?
+        .strip_prefix(name)?
+        .strip_prefix(" \"")?
+        .strip_suffix("\"]")
+}
+
+fn strip_move_number(token: &str) -> &str {
+    let rest = token.trim_start_matches(|c: char| c.is_ascii_digit());
+    if rest.len() < token.len() && rest.starts_with('.') {
+        rest.trim_start_matches('.')
+    } else {
+        token
+    }
+}
+
+impl State {
+    pub(crate) fn to_game_text(&self) -> Result<String> {
+        let (mut board, player) = Board::from_fen(&self.start_fen)?;
+        board.set_variant(self.variant);
+        let mut replay = State {
+            player,
+            ..State::default()
+        };
+
+        let mut tokens = vec![];
+        for (idx, record) in self.history.records().iter().enumerate() {
+            let mov = board
+                .legal_moves(record.src())
+                .into_iter()
+                .find(|mov| {
+                    mov.move_to == record.dest()
+                        && mov.move_type.promotion() == record.move_type().promotion()
+                })
+                .ok_or_else(|| anyhow!("Recorded move {} is not legal", record.label()))?;
+
+            match replay.player {
+                Player::White => tokens.push(format!("{}.", board.fullmove_number())),
+                Player::Black if idx == 0 => tokens.push(format!("{}...", board.fullmove_number())),
+                Player::Black => {}
+            }
+            tokens.push(to_san(&mut board, record.src(), mov));
+
+            replay.play(&mut board, record.src(), mov)?;
+        }
+
+        let result = self
+            .result
+            .map_or("*".to_string(), |result| result.score.label());
+        tokens.push(result.clone());
+        let termination = self
+            .status
+            .termination()
+            .map(|termination| format!("[Termination \"{termination}\"]\n"))
+            .unwrap_or_default();
+
+        let movetext = tokens
+            .chunks(MOVES_PER_LINE * 3)
+            .map(|line| line.join(" "))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Ok(format!(
+            "[FEN \"{}\"]\n[Variant \"{}\"]\n[Scoring \"{}\"]\n[Result \"{result}\"]\n{termination}\n{movetext}\n",
+            self.start_fen,
+            self.variant.label(),
+            self.scoring.label()
+        ))
+    }
+
+    pub(crate) fn from_game_text(text: &str) -> Result<(Board, Self)> {
+        let variant = match text.lines().find_map(|line| tag(line, "Variant")) {
+            Some(label) => {
+                Variant::from_label(label).ok_or_else(|| anyhow!("Unknown variant {label:?}"))?
+            }
+            None => Variant::default(),
+        };
+        let scoring = match text.lines().find_map(|line| tag(line, "Scoring")) {
+            Some(label) => ScoringRule::from_label(label)
+                .ok_or_else(|| anyhow!("Unknown scoring rule {label:?}"))?,
+            None => ScoringRule::default(),
+        };
+        let start_fen = match text.lines().find_map(|line| tag(line, "FEN")) {
+            Some(fen) => fen.to_string(),
+            None => State::for_board(&Board::with_depth(Depth::default(), variant)).start_fen,
+        };
+
+        let (mut board, player) =
+            Board::from_fen(&start_fen).context("Invalid starting position")?;
+        board.set_variant(variant);
+        let mut state = State {
+            player,
+            start_fen,
+            variant,
+            scoring,
+            history: History::starting_at(board.fullmove_number(), player),
+            ..State::for_board(&board)
+        };
+        state.update_status(&mut board);
+
+        let movetext = text
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('['))
+            .flat_map(str::split_whitespace)
+            .map(strip_move_number)
+            .filter(|token| !token.is_empty() && *token != "*" && !Score::is_label(token));
+
+        for (idx, san) in movetext.enumerate() {
+            let (src, mov) = parse_san(&mut board, state.player, san)
+                .with_context(|| format!("Move {} ({san:?}) cannot be played", idx + 1))?;
+            state.play(&mut board, src, mov)?;
+        }
+
+        // A flag fall leaves nothing in the moves, so the game is only known
+        // to be over from its tag.
+        if let Some(label) = text.lines().find_map(|line| tag(line, "Termination")) {
+            let status = GameStatus::from_termination(label)
+                .ok_or_else(|| anyhow!("Unknown termination {label:?}"))?;
+            if !state.status.is_terminal() {
+                state.status = status;
+                state.result = GameResult::new(status, state.player, scoring);
+            }
+        }
+
+        Ok((board, state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        board::Board,
+        state::{GameStatus, State},
+        utils::{notation::parse_san, player::Player, scoring::ScoringRule, variant::Variant},
+    };
+
+    fn load(text: &str) -> anyhow::Result<(Board, State)> {
+        State::from_game_text(text)
+    }
+
+    fn play_all(board: &mut Board, state: &mut State, moves: &[&str]) {
+        for san in moves {
+            let (src, mov) = parse_san(board, state.player, san).unwrap();
+            state.play(board, src, mov).unwrap();
+        }
+    }
+
+    #[test]
+    fn saved_games_reload_with_full_history() {
+        let mut board = Board::new();
+        let mut state = State::new();
+        play_all(
+            &mut board,
+            &mut state,
+            &["e6", "d6", "exd6", "e6", "Qe3", "Qe8"],
+        );
+
+        let text = state.to_game_text().unwrap();
+        assert!(text.starts_with(&format!("[FEN \"{}\"]", state.start_fen)));
+        assert!(text.contains("1. e6 d6 2. exd6 e6 3. Qe3 Qe8"), "{text}");
+
+        let (mut loaded, mut loaded_state) = load(&text).unwrap();
+        assert_eq!(
+            loaded.to_fen(loaded_state.player),
+            board.to_fen(state.player)
+        );
+        assert_eq!(loaded_state.history.lines(), state.history.lines());
+
+        loaded_state.undo(&mut loaded);
+        loaded_state.undo(&mut loaded);
+        assert!(matches!(loaded_state.player, Player::White));
+        loaded_state.redo(&mut loaded);
+        loaded_state.redo(&mut loaded);
+        assert_eq!(
+            loaded.to_fen(loaded_state.player),
+            board.to_fen(state.player)
+        );
+    }
+
+    #[test]
+    fn custom_starting_positions_are_kept() {
+        let fen = "6/Q6/8/9/10/5K4k/10/9/8/7/6 b - 3 20";
+        let text = format!("[FEN \"{fen}\"]\n\n20... Kf10 21. Qb3\n");
+
+        let (mut board, mut state) = load(&text).unwrap();
+        assert_eq!(state.start_fen, fen);
+        assert!(matches!(state.player, Player::Black));
+
+        let saved = state.to_game_text().unwrap();
+        assert!(saved.contains("20... Kf10 21. Qb3"), "{saved}");
+
+        state.undo(&mut board);
+        state.undo(&mut board);
+        assert_eq!(board.to_fen(state.player), fen);
+        assert_eq!(state.status, GameStatus::InProgress);
+    }
+
+    #[test]
+    fn variants_are_kept() {
+        let mut board = Board::with_variant(Variant::McCooey);
+        let mut state = State::for_board(&board);
+        play_all(&mut board, &mut state, &["f5", "f7"]);
+
+        let text = state.to_game_text().unwrap();
+        assert!(text.contains("[Variant \"mccooey\"]"), "{text}");
+
+        let (loaded, loaded_state) = load(&text).unwrap();
+        assert_eq!(loaded.variant(), Variant::McCooey);
+        assert_eq!(loaded_state.history.lines(), state.history.lines());
+
+        let (_, untagged) = load("[Variant \"mccooey\"]\n\n1. f5").unwrap();
+        assert_eq!(untagged.start_fen, state.start_fen);
+    }
+
+    #[test]
+    fn castling_is_written_in_san_and_undone_with_its_rights() {
+        let fen = "1R4/7/8/1K7/10/11/R9/7k1/8/7/6 w KQ - 0 1";
+        let text = format!("[FEN \"{fen}\"]\n[Variant \"shafran\"]\n\n1. O-O Kh9 2. Rac2");
+
+        let (mut board, mut state) = load(&text).unwrap();
+        assert_eq!(state.history.records()[0].label(), "O-O");
+        assert!(board.castling().is_empty());
+
+        let saved = state.to_game_text().unwrap();
+        assert!(saved.contains("1. O-O Kh9 2. Rac2"), "{saved}");
+
+        for _ in 0..3 {
+            state.undo(&mut board);
+        }
+        assert_eq!(board.to_fen(state.player), fen);
+
+        let (_, queenside) = load(&format!(
+            "[FEN \"{fen}\"]\n[Variant \"shafran\"]\n\n1. 0-0-0"
+        ))
+        .unwrap();
+        assert_eq!(queenside.history.records()[0].label(), "O-O-O");
+    }
+
+    #[test]
+    fn illegal_moves_are_reported_by_position() {
+        let err = load("1. e6 d6 2. Qe9").err().unwrap();
+        assert!(err.to_string().contains("Move 3 (\"Qe9\")"), "{err}");
+
+        let err = load("[FEN \"6/7 w - 0 1\"]\n").err().unwrap();
+        assert!(
+            err.to_string().contains("Invalid starting position"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn results_are_written_with_the_chosen_scoring() {
+        let fen = "k5/2R4/2K5/9/10/11/10/9/8/7/6 w - 0 1";
+        let (_, mut state) = load(&format!("[FEN \"{fen}\"]\n\n1. Rd5")).unwrap();
+        assert_eq!(state.status, GameStatus::Stalemate);
+
+        let result = state.result.unwrap();
+        assert_eq!(result.winner, Some(Player::White));
+        assert_eq!(result.reason, GameStatus::Stalemate);
+
+        let saved = state.to_game_text().unwrap();
+        assert!(saved.contains("[Scoring \"glinski\"]"), "{saved}");
+        assert!(saved.contains("[Result \"3/4-1/4\"]"), "{saved}");
+        assert!(saved.trim_end().ends_with("1. Rd5 3/4-1/4"), "{saved}");
+
+        let (_, reloaded) = load(&saved).unwrap();
+        assert_eq!(reloaded.result, Some(result));
+
+        state.set_scoring(ScoringRule::FideStyle);
+        assert_eq!(state.result.unwrap().winner, None);
+        let saved = state.to_game_text().unwrap();
+        assert!(saved.contains("[Scoring \"fide-style\"]"), "{saved}");
+        assert!(saved.contains("1. Rd5 1/2-1/2"), "{saved}");
+
+        let (_, reloaded) = load(&saved).unwrap();
+        assert_eq!(reloaded.scoring, ScoringRule::FideStyle);
+        assert_eq!(reloaded.result, state.result);
+    }
+}
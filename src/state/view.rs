@@ -0,0 +1,311 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Color,
+    text::Line,
+    widgets::{
+        Block, Borders, Widget,
+        canvas::{Canvas, Context},
+    },
+};
+
+use crate::utils::{consts::TONE_CANVAS_BG, mark::Mark, player::Player};
+
+use super::{GameStatus, State};
+
+impl GameStatus {
+    fn color(self) -> Color {
+        match self {
+            Self::InProgress => Color::LightYellow,
+            Self::Check => Color::LightYellow,
+            Self::Checkmate | Self::TimeForfeit => Color::Red,
+            Self::Stalemate => Color::LightGreen,
+            Self::InsufficientMaterial | Self::ThreefoldRepetition | Self::FiftyMoveRule => {
+                Color::LightGreen
+            }
+        }
+    }
+}
+
+fn draw_mark(
+    ctx: &mut Context<'_>,
+    mark: char,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    color: Color,
+) {
+    match mark {
+        'A' => ctx.draw(&Mark::<'A'>::new(x, y, width, height, color)),
+        'B' => ctx.draw(&Mark::<'B'>::new(x, y, width, height, color)),
+        'C' => ctx.draw(&Mark::<'C'>::new(x, y, width, height, color)),
+        'D' => ctx.draw(&Mark::<'D'>::new(x, y, width, height, color)),
+        'E' => ctx.draw(&Mark::<'E'>::new(x, y, width, height, color)),
+        'H' => ctx.draw(&Mark::<'H'>::new(x, y, width, height, color)),
+        'I' => ctx.draw(&Mark::<'I'>::new(x, y, width, height, color)),
+        'K' => ctx.draw(&Mark::<'K'>::new(x, y, width, height, color)),
+        'L' => ctx.draw(&Mark::<'L'>::new(x, y, width, height, color)),
+        'M' => ctx.draw(&Mark::<'M'>::new(x, y, width, height, color)),
+        'N' => ctx.draw(&Mark::<'N'>::new(x, y, width, height, color)),
+        'O' => ctx.draw(&Mark::<'O'>::new(x, y, width, height, color)),
+        'R' => ctx.draw(&Mark::<'R'>::new(x, y, width, height, color)),
+        'S' => ctx.draw(&Mark::<'S'>::new(x, y, width, height, color)),
+        'T' => ctx.draw(&Mark::<'T'>::new(x, y, width, height, color)),
+        'U' => ctx.draw(&Mark::<'U'>::new(x, y, width, height, color)),
+        'W' => ctx.draw(&Mark::<'W'>::new(x, y, width, height, color)),
+        'Y' => ctx.draw(&Mark::<'Y'>::new(x, y, width, height, color)),
+        _ => {}
+    }
+}
+
+fn draw_word(
+    ctx: &mut Context<'_>,
+    word: &str,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    color: Color,
+) {
+    let gap = width * 0.35;
+    let count = word.chars().count() as f64;
+    let total_width = count * width + (count - 1.).max(0.) * gap;
+    let start = x - total_width / 2. + width / 2.;
+
+    word.chars().enumerate().for_each(|(idx, mark)| {
+        draw_mark(
+            ctx,
+            mark,
+            start + idx as f64 * (width + gap),
+            y,
+            width,
+            height,
+            color,
+        );
+    });
+}
+
+fn mark_width_for(word: &str, max_width: f64, height: f64) -> f64 {
+    const GAP_FACTOR: f64 = 0.35;
+
+    let count = word.chars().count() as f64;
+    let fit_width = max_width / (count + (count - 1.).max(0.) * GAP_FACTOR);
+
+    (height * 0.75).min(fit_width * 0.9)
+}
+
+impl State {
+    fn panel_status(&self) -> Option<(&'static str, Color)> {
+        if self.is_promoting() {
+            return Some(("PROMOTE", Color::LightYellow));
+        }
+
+        self.status
+            .label()
+            .map(|label| (label, self.status.color()))
+    }
+}
+
+// The panel's turn, status and history boxes, top to bottom.
+fn areas(inner: Rect) -> [Rect; 3] {
+    Layout::vertical([
+        Constraint::Percentage(20),
+        Constraint::Percentage(15),
+        Constraint::Percentage(65),
+    ])
+    .areas(inner)
+}
+
+// Where the move list starts below the history heading, and how far apart
+// its rows are.
+fn history_rows(history_area: Rect) -> (f64, f64, usize) {
+    let history_y_dim = history_area.height as f64;
+    let inner_rows = history_area.height.saturating_sub(2).max(1) as f64;
+    let row_height = history_y_dim / inner_rows;
+    let list_top = history_y_dim * 0.32;
+    let rows = ((list_top + history_y_dim / 2.) / row_height).floor() as usize;
+
+    (list_top, row_height, rows)
+}
+
+impl State {
+    /// How many moves the history lists when the panel is drawn in `area`.
+    pub(crate) fn history_rows(area: Rect) -> usize {
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        let [_, _, history_area] = areas(inner);
+
+        history_rows(history_area).2
+    }
+}
+
+impl Widget for &State {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer)
+    where
+        Self: Sized,
+    {
+        let block = Block::default().borders(Borders::ALL);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [player_area, status_area, history_area] = areas(inner);
+
+        let (player_area, clock_area) = match self.clock {
+            Some(_) => {
+                let [player_area, clock_area] =
+                    Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                        .areas(player_area);
+                (player_area, Some(clock_area))
+            }
+            None => (player_area, None),
+        };
+
+        let player_y_dim = player_area.height as f64;
+        let player_x_dim = player_area.width as f64;
+        let status_y_dim = status_area.height as f64;
+        let status_x_dim = status_area.width as f64;
+        let history_y_dim = history_area.height as f64;
+        let history_x_dim = history_area.width as f64;
+
+        let player = Canvas::default()
+            .block(Block::default().borders(Borders::ALL))
+            .background_color(TONE_CANVAS_BG)
+            .x_bounds([-player_x_dim / 2., player_x_dim / 2.])
+            .y_bounds([-player_y_dim / 2., player_y_dim / 2.])
+            .paint(|ctx| {
+                let heading_height = player_y_dim * 0.18;
+                let heading_width = mark_width_for("TURN", player_x_dim * 0.8, heading_height);
+                let player_mark_size = (player_y_dim * 0.38).min(player_x_dim * 0.45);
+
+                draw_word(
+                    ctx,
+                    "TURN",
+                    0.,
+                    player_y_dim * 0.24,
+                    heading_width,
+                    heading_height,
+                    Color::LightYellow,
+                );
+
+                match self.player {
+                    Player::White => ctx.draw(&Mark::<'W'>::new(
+                        0.,
+                        -player_y_dim * 0.16,
+                        player_mark_size,
+                        player_mark_size,
+                        Color::White,
+                    )),
+                    Player::Black => ctx.draw(&Mark::<'B'>::new(
+                        0.,
+                        -player_y_dim * 0.16,
+                        player_mark_size,
+                        player_mark_size,
+                        Color::Red,
+                    )),
+                }
+            });
+
+        let status = Canvas::default()
+            .block(Block::default().borders(Borders::ALL))
+            .background_color(TONE_CANVAS_BG)
+            .x_bounds([-status_x_dim / 2., status_x_dim / 2.])
+            .y_bounds([-status_y_dim / 2., status_y_dim / 2.])
+            .paint(|ctx| {
+                let score = self.result.map(|result| result.score.label());
+                let (label_y, label_height) = match score {
+                    Some(_) => (status_y_dim * 0.12, status_y_dim * 0.4),
+                    None => (0., status_y_dim * 0.5),
+                };
+
+                if let Some((label, color)) = self.panel_status() {
+                    let label_width = mark_width_for(label, status_x_dim * 0.85, label_height);
+
+                    draw_word(ctx, label, 0., label_y, label_width, label_height, color);
+                }
+
+                if let Some(score) = score {
+                    let inner_cols = status_area.width.saturating_sub(2).max(1) as f64;
+                    let col_width = status_x_dim / inner_cols;
+
+                    ctx.print(
+                        -(score.len() as f64) * col_width / 2.,
+                        -status_y_dim * 0.3,
+                        score,
+                    );
+                }
+            });
+
+        let history = Canvas::default()
+            .block(Block::default().borders(Borders::ALL))
+            .background_color(TONE_CANVAS_BG)
+            .x_bounds([-history_x_dim / 2., history_x_dim / 2.])
+            .y_bounds([-history_y_dim / 2., history_y_dim / 2.])
+            .paint(|ctx| {
+                let heading_height = history_y_dim * 0.08;
+                let heading_width = mark_width_for("HISTORY", history_x_dim * 0.85, heading_height);
+
+                draw_word(
+                    ctx,
+                    "HISTORY",
+                    0.,
+                    history_y_dim * 0.42,
+                    heading_width,
+                    heading_height,
+                    Color::LightYellow,
+                );
+
+                let inner_cols = history_area.width.saturating_sub(2).max(1) as f64;
+                let col_width = history_x_dim / inner_cols;
+                let (list_top, row_height, rows) = history_rows(history_area);
+
+                self.history
+                    .visible_lines(rows)
+                    .into_iter()
+                    .enumerate()
+                    .for_each(|(idx, line)| {
+                        ctx.print(
+                            -history_x_dim / 2. + col_width,
+                            list_top - idx as f64 * row_height,
+                            line,
+                        );
+                    });
+            });
+
+        if let (Some(clock), Some(clock_area)) = (self.clock, clock_area) {
+            let clock_y_dim = clock_area.height as f64;
+            let clock_x_dim = clock_area.width as f64;
+            let inner_rows = clock_area.height.saturating_sub(2).max(1) as f64;
+            let row_height = clock_y_dim / inner_rows;
+            let inner_cols = clock_area.width.saturating_sub(2).max(1) as f64;
+            let col_width = clock_x_dim / inner_cols;
+
+            Canvas::default()
+                .block(Block::default().borders(Borders::ALL))
+                .background_color(TONE_CANVAS_BG)
+                .x_bounds([-clock_x_dim / 2., clock_x_dim / 2.])
+                .y_bounds([-clock_y_dim / 2., clock_y_dim / 2.])
+                .paint(|ctx| {
+                    for (idx, player) in [Player::White, Player::Black].into_iter().enumerate() {
+                        let text = format!("{} {}", player.label(), clock.label(player));
+                        let color = if clock.is_flagged(player) {
+                            Color::Red
+                        } else if player == self.player {
+                            Color::LightYellow
+                        } else {
+                            Color::Gray
+                        };
+
+                        ctx.print(
+                            -(text.len() as f64) * col_width / 2.,
+                            (0.5 - idx as f64) * row_height,
+                            Line::from(text).style(color),
+                        );
+                    }
+                })
+                .render(clock_area, buf);
+        }
+
+        player.render(player_area, buf);
+        status.render(status_area, buf);
+        history.render(history_area, buf);
+    }
+}
@@ -1,19 +1,25 @@
+#[cfg(feature = "tui")]
 use std::ops::Sub;
 
 use cell::Cell;
+#[cfg(feature = "tui")]
 use repr::Repr;
 
-use crate::utils::{delta::Delta, file::File, rank::Rank};
+#[cfg(feature = "tui")]
+use crate::utils::{delta::Delta, direction::Direction};
+#[cfg(feature = "tui")]
 use anyhow::Result;
 
 pub(crate) mod cell;
 pub(crate) mod repr;
 
+#[cfg(feature = "tui")]
 #[derive(Debug, Clone, Copy, Default)]
 pub(crate) struct UnitHexagon {
     cell: Cell,
 }
 
+#[cfg(feature = "tui")]
 impl Sub for UnitHexagon {
     type Output = Delta<isize>;
     fn sub(self, rhs: Self) -> Self::Output {
@@ -21,27 +27,27 @@ impl Sub for UnitHexagon {
     }
 }
 
+#[cfg(feature = "tui")]
 impl UnitHexagon {
-    pub(crate) fn try_new(rank: Rank, file: File) -> Result<Self> {
-        Ok(Self {
-            cell: Cell::try_new(rank, file)?,
-        })
+    pub(crate) fn try_new(cell: Cell) -> Result<Self> {
+        Cell::try_new_in(cell.depth, cell.rank, cell.file).map(|cell| Self { cell })
     }
 
     pub(crate) fn cell(&self) -> Cell {
         self.cell
     }
 
-    pub(crate) fn new(rank: Rank, file: File) -> Self {
-        Self::try_new(rank, file).unwrap()
-    }
+    pub(crate) fn center_wrt(&self, other: Self) -> Delta<f64> {
+        let delta = Repr::from(self.cell) - Repr::from(other.cell);
 
-    pub(crate) fn center(&self) -> Delta<f64> {
-        self.center_wrt(Self::default())
+        delta.cast() * (1.5, 3_f64.sqrt() / 2.)
     }
 
-    pub(crate) fn center_wrt(&self, other: Self) -> Delta<f64> {
-        let delta = Repr::from(self.cell) - Repr::from(other.cell);
+    /// How far the centre moves on one step in `direction`, whether or not
+    /// the step stays on the board.
+    pub(crate) fn step(&self, direction: Direction) -> Delta<f64> {
+        let repr = Repr::from(self.cell);
+        let delta = repr.next(direction) - repr;
 
         delta.cast() * (1.5, 3_f64.sqrt() / 2.)
     }
@@ -1,24 +1,23 @@
-use crate::utils::{Step, direction::Direction, file::File, range::Range, rank::Rank};
+use crate::utils::{depth::Depth, direction::Direction, file::File, rank::Rank};
+
+use super::repr::Repr;
 use anyhow::{Result, anyhow};
-use ratatui::text::Line;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
-pub(crate) struct Cell {
+pub struct Cell {
     pub(crate) rank: Rank,
     pub(crate) file: File,
+    pub(crate) depth: Depth,
 }
 
 impl Cell {
-    pub(crate) fn try_new(rank: Rank, file: File) -> Result<Self> {
-        if match rank {
-            Rank::Rank7 => file < File::FileL && file > File::FileA,
-            Rank::Rank8 => file < File::FileK && file > File::FileB,
-            Rank::Rank9 => file < File::FileI && file > File::FileC,
-            Rank::Rank10 => file < File::FileH && file > File::FileD,
-            Rank::Rank11 => file < File::FileG && file > File::FileE,
-            _ => true,
-        } {
-            Ok(Self { rank, file })
+    pub fn try_new(rank: Rank, file: File) -> Result<Self> {
+        Self::try_new_in(Depth::default(), rank, file)
+    }
+
+    pub fn try_new_in(depth: Depth, rank: Rank, file: File) -> Result<Self> {
+        if depth.contains(rank, file) {
+            Ok(Self { rank, file, depth })
         } else {
             Err(anyhow!(
                 "Invalid rank and file combination, found rank: {:?}, file: {:?}",
@@ -29,152 +28,98 @@ impl Cell {
     }
 
     pub(crate) const unsafe fn from_raw_parts(rank: Rank, file: File) -> Self {
-        Self { rank, file }
+        Self {
+            rank,
+            file,
+            depth: Depth::GLINSKI,
+        }
     }
 
-    pub(crate) fn new(rank: Rank, file: File) -> Self {
+    pub fn new(rank: Rank, file: File) -> Self {
         Self::try_new(rank, file).unwrap()
     }
 
-    pub(crate) fn label(&self) -> String {
+    pub fn new_in(depth: Depth, rank: Rank, file: File) -> Self {
+        Self::try_new_in(depth, rank, file).unwrap()
+    }
+
+    pub fn rank(&self) -> Rank {
+        self.rank
+    }
+
+    pub fn file(&self) -> File {
+        self.file
+    }
+
+    pub fn depth(&self) -> Depth {
+        self.depth
+    }
+
+    pub fn label(&self) -> String {
         format!("{}{}", self.file.label(), self.rank.label())
     }
 
-    pub(crate) fn line(&self) -> Line<'static> {
-        Line::from(format!("C: {}", self.label()))
+    pub fn from_label(label: &str) -> Result<Self> {
+        Self::from_label_in(Depth::default(), label)
     }
 
-    pub(crate) fn next(&self, direction: Direction) -> Option<Self> {
-        let (rank, file) = match direction {
-            Direction::Clock1 => {
-                let rank = if self.file < File::FileF {
-                    self.rank.succ().and_then(|r| r.succ())
-                } else {
-                    self.rank.succ()
-                };
-
-                let file = self.file.succ();
-
-                (rank, file)
-            }
-            Direction::Clock2 => {
-                let rank = if self.file < File::FileF {
-                    self.rank.succ()
-                } else {
-                    Some(self.rank)
-                };
-
-                let file = self.file.succ();
-
-                (rank, file)
-            }
-            Direction::Clock3 => {
-                let rank = if self.file == File::FileE {
-                    Some(self.rank)
-                } else if self.file < File::FileF {
-                    self.rank.succ()
-                } else {
-                    self.rank.pred()
-                };
-
-                let file = self.file.succ().and_then(|f| f.succ());
-
-                (rank, file)
-            }
-            Direction::Clock4 => {
-                let rank = if self.file < File::FileF {
-                    Some(self.rank)
-                } else {
-                    self.rank.pred()
-                };
-
-                let file = self.file.succ();
-
-                (rank, file)
-            }
-            Direction::Clock5 => {
-                let rank = if self.file < File::FileF {
-                    self.rank.pred()
-                } else {
-                    self.rank.pred().and_then(|r| r.pred())
-                };
-
-                let file = self.file.succ();
-                (rank, file)
-            }
-            Direction::Clock6 => {
-                let rank = self.rank.pred();
-                let file = Some(self.file);
-
-                (rank, file)
-            }
-            Direction::Clock7 => {
-                let rank = if self.file > File::FileF {
-                    self.rank.pred()
-                } else {
-                    self.rank.pred().and_then(|r| r.pred())
-                };
-
-                let file = self.file.pred();
-
-                (rank, file)
-            }
-            Direction::Clock8 => {
-                let rank = if self.file > File::FileF {
-                    Some(self.rank)
-                } else {
-                    self.rank.pred()
-                };
-
-                let file = self.file.pred();
-
-                (rank, file)
-            }
-            Direction::Clock9 => {
-                let rank = if self.file == File::FileG {
-                    Some(self.rank)
-                } else if self.file > File::FileF {
-                    self.rank.succ()
-                } else {
-                    self.rank.pred()
-                };
-                let file = self.file.pred().and_then(|f| f.pred());
-
-                (rank, file)
-            }
-            Direction::Clock10 => {
-                let rank = if self.file > File::FileF {
-                    self.rank.succ()
-                } else {
-                    Some(self.rank)
-                };
-
-                let file = self.file.pred();
-                (rank, file)
-            }
-            Direction::Clock11 => {
-                let rank = if self.file > File::FileF {
-                    self.rank.succ().and_then(|r| r.succ())
-                } else {
-                    self.rank.succ()
-                };
-
-                let file = self.file.pred();
-
-                (rank, file)
-            }
-            Direction::Clock12 => {
-                let rank = self.rank.succ();
-                let file = Some(self.file);
-
-                (rank, file)
-            }
-        };
-
-        match (rank, file) {
-            (Some(r), Some(f)) => Self::try_new(r, f).ok(),
-            _ => None,
-        }
+    pub fn from_label_in(depth: Depth, label: &str) -> Result<Self> {
+        let mut chars = label.chars();
+        let file = chars
+            .next()
+            .and_then(|file| File::from_label(&file.to_string()))
+            .ok_or_else(|| anyhow!("Invalid file in cell label: {label:?}"))?;
+        let rank = Rank::from_label(chars.as_str())
+            .ok_or_else(|| anyhow!("Invalid rank in cell label: {label:?}"))?;
+
+        Self::try_new_in(depth, rank, file)
+    }
+
+    pub fn next(&self, direction: Direction) -> Option<Self> {
+        Repr::from(*self).next(direction).cell_in(self.depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{depth::Depth, direction::Direction, file::File, rank::Rank};
+
+    use super::Cell;
+
+    #[test]
+    fn bigger_boards_are_labelled_past_l() {
+        let depth = Depth::new(8).unwrap();
+        let centre = depth.centre();
+        assert_eq!(centre.label(), "H8");
+
+        let edge = centre
+            .next(Direction::Clock3)
+            .unwrap()
+            .next(Direction::Clock3);
+        assert_eq!(edge.unwrap().label(), "M6");
+        assert_eq!(Cell::from_label_in(depth, "P1").unwrap().file, File::FileP);
+        assert!(Cell::from_label_in(depth, "P9").is_err());
+        assert!(Cell::from_label("M1").is_err());
+    }
+
+    #[test]
+    fn steps_stay_on_their_own_board() {
+        let depth = Depth::new(7).unwrap();
+        let corner = Cell::new_in(depth, Rank::Rank1, File::FileA);
+
+        assert!(corner.next(Direction::Clock6).is_none());
+        assert_eq!(
+            corner.next(Direction::Clock12),
+            Some(Cell::new_in(depth, Rank::Rank2, File::FileA))
+        );
+        assert_eq!(
+            corner.next(Direction::Clock2),
+            Some(Cell::new_in(depth, Rank::Rank2, File::FileB))
+        );
+        assert_eq!(
+            Cell::new(Rank::Rank1, File::FileL).next(Direction::Clock4),
+            None
+        );
     }
 }
 
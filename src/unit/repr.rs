@@ -2,7 +2,7 @@ use std::ops::Sub;
 
 use anyhow::{Result, anyhow};
 
-use crate::utils::{delta::Delta, direction::Direction, file::File, rank::Rank};
+use crate::utils::{delta::Delta, depth::Depth, direction::Direction, file::File, rank::Rank};
 
 use super::Cell;
 
@@ -21,8 +21,9 @@ impl Sub for Repr {
 
 impl From<Cell> for Repr {
     fn from(value: Cell) -> Self {
-        let x = value.file - File::default();
-        let y = x.abs() + (value.rank - Rank::default()) * 2;
+        let radius = value.depth.radius();
+        let x = value.file.index() as isize - radius;
+        let y = x.abs() + (value.rank.index() as isize - radius) * 2;
 
         Self::new(x, y)
     }
@@ -61,4 +62,16 @@ impl Repr {
 
         Self::new(x, y)
     }
+
+    /// The cell at this position on a board of the given depth, if it is on
+    /// the board at all.
+    pub(crate) fn cell_in(&self, depth: Depth) -> Option<Cell> {
+        let radius = depth.radius();
+        let file = File::from_index(self.x + radius)?;
+        let rank = Rank::from_index((self.y - self.x.abs()) / 2 + radius)?;
+
+        depth
+            .contains(rank, file)
+            .then(|| Cell::new_in(depth, rank, file))
+    }
 }
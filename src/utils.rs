@@ -1,25 +1,43 @@
-use ratatui::style::Color;
-
 use crate::unit::cell::Cell;
 
+pub(crate) mod castling;
+#[cfg(feature = "tui")]
+pub(crate) mod clock;
+#[cfg(feature = "tui")]
 pub(crate) mod consts;
+#[cfg(feature = "tui")]
+pub(crate) mod controller;
 pub(crate) mod delta;
 pub(crate) mod depth;
 pub(crate) mod direction;
 pub(crate) mod entry;
 pub(crate) mod file;
+#[cfg(feature = "tui")]
 pub(crate) mod fill_mode;
+#[cfg(feature = "tui")]
+pub(crate) mod game_result;
+#[cfg(feature = "tui")]
 pub(crate) mod history;
+#[cfg(feature = "tui")]
+pub(crate) mod keymap;
+#[cfg(feature = "tui")]
 pub(crate) mod mark;
+#[cfg(feature = "tui")]
 pub(crate) mod mode;
 pub(crate) mod moves;
+#[cfg(feature = "tui")]
+pub(crate) mod notation;
 pub(crate) mod player;
+#[cfg(feature = "tui")]
 pub(crate) mod progression;
+#[cfg(feature = "tui")]
+pub(crate) mod prompt;
 pub(crate) mod range;
 pub(crate) mod rank;
-pub(crate) mod stack;
+#[cfg(feature = "tui")]
+pub(crate) mod scoring;
+pub(crate) mod variant;
 
 pub(crate) trait Step: Sized {
     fn succ(&self) -> Option<Self>;
-    fn pred(&self) -> Option<Self>;
 }
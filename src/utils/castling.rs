@@ -0,0 +1,136 @@
+use crate::unit::cell::Cell;
+
+use super::{direction::Direction, player::Player};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Wing {
+    King,
+    Queen,
+}
+
+impl Wing {
+    pub fn san(self) -> &'static str {
+        match self {
+            Self::King => "O-O",
+            Self::Queen => "O-O-O",
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Self::King => 0,
+            Self::Queen => 1,
+        }
+    }
+}
+
+/// Where a castling king and rook start, and which way the king walks to
+/// reach the rook. The king always moves two cells and the rook lands on the
+/// cell the king passed over.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Castling {
+    pub(crate) wing: Wing,
+    pub(crate) king: Cell,
+    pub(crate) rook: Cell,
+    pub(crate) direction: Direction,
+}
+
+impl Castling {
+    pub(crate) fn king_to(&self) -> Option<Cell> {
+        self.king.next(self.direction)?.next(self.direction)
+    }
+
+    pub(crate) fn rook_to(&self) -> Option<Cell> {
+        self.king.next(self.direction)
+    }
+
+    pub(crate) fn between(&self) -> impl Iterator<Item = Cell> + '_ {
+        std::iter::successors(self.king.next(self.direction), |cell| {
+            cell.next(self.direction)
+        })
+        .take_while(|cell| *cell != self.rook)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct CastlingRights {
+    white: [bool; 2],
+    black: [bool; 2],
+}
+
+impl CastlingRights {
+    const LABELS: [(Player, Wing, char); 4] = [
+        (Player::White, Wing::King, 'K'),
+        (Player::White, Wing::Queen, 'Q'),
+        (Player::Black, Wing::King, 'k'),
+        (Player::Black, Wing::Queen, 'q'),
+    ];
+
+    fn side(&self, color: Player) -> &[bool; 2] {
+        match color {
+            Player::White => &self.white,
+            Player::Black => &self.black,
+        }
+    }
+
+    fn side_mut(&mut self, color: Player) -> &mut [bool; 2] {
+        match color {
+            Player::White => &mut self.white,
+            Player::Black => &mut self.black,
+        }
+    }
+
+    pub(crate) fn has(&self, color: Player, wing: Wing) -> bool {
+        self.side(color)[wing.index()]
+    }
+
+    pub(crate) fn grant(&mut self, color: Player, wing: Wing) {
+        self.side_mut(color)[wing.index()] = true;
+    }
+
+    pub(crate) fn revoke(&mut self, color: Player, wing: Wing) {
+        self.side_mut(color)[wing.index()] = false;
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Indices of the rights still held, for hashing.
+    #[cfg(feature = "tui")]
+    pub(crate) fn held(&self) -> impl Iterator<Item = u64> + '_ {
+        Self::LABELS
+            .iter()
+            .enumerate()
+            .filter(|(_, (color, wing, _))| self.has(*color, *wing))
+            .map(|(idx, _)| idx as u64)
+    }
+
+    pub(crate) fn label(&self) -> String {
+        let label = Self::LABELS
+            .iter()
+            .filter(|(color, wing, _)| self.has(*color, *wing))
+            .map(|(_, _, letter)| letter)
+            .collect::<String>();
+
+        if label.is_empty() {
+            "-".to_string()
+        } else {
+            label
+        }
+    }
+
+    pub(crate) fn from_label(label: &str) -> Option<Self> {
+        let mut rights = Self::default();
+        if label == "-" {
+            return Some(rights);
+        }
+
+        for letter in label.chars() {
+            let (color, wing, _) = Self::LABELS.iter().find(|(_, _, known)| *known == letter)?;
+            rights.grant(*color, *wing);
+        }
+
+        Some(rights)
+    }
+}
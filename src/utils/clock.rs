@@ -0,0 +1,140 @@
+use std::time::Duration;
+
+use super::player::Player;
+
+/// Time given back to a player after each of their moves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bonus {
+    None,
+    /// Fischer: the full increment is added after every move.
+    Increment(Duration),
+    /// Bronstein: the time used on the move is given back, up to the delay.
+    Delay(Duration),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeControl {
+    pub base: Duration,
+    pub bonus: Bonus,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Clock {
+    control: TimeControl,
+    white: Duration,
+    black: Duration,
+    spent: Duration,
+}
+
+impl Clock {
+    pub(crate) fn new(control: TimeControl) -> Self {
+        Self {
+            control,
+            white: control.base,
+            black: control.base,
+            spent: Duration::ZERO,
+        }
+    }
+
+    pub(crate) fn remaining(&self, player: Player) -> Duration {
+        match player {
+            Player::White => self.white,
+            Player::Black => self.black,
+        }
+    }
+
+    fn remaining_mut(&mut self, player: Player) -> &mut Duration {
+        match player {
+            Player::White => &mut self.white,
+            Player::Black => &mut self.black,
+        }
+    }
+
+    pub(crate) fn is_flagged(&self, player: Player) -> bool {
+        self.remaining(player).is_zero()
+    }
+
+    /// Runs `player`'s clock down by `elapsed`.
+    pub(crate) fn tick(&mut self, player: Player, elapsed: Duration) {
+        let remaining = self.remaining_mut(player);
+        *remaining = remaining.saturating_sub(elapsed);
+        self.spent += elapsed;
+    }
+
+    /// Stops `player`'s clock after their move and starts the opponent's.
+    pub(crate) fn press(&mut self, player: Player) {
+        let bonus = match self.control.bonus {
+            Bonus::None => Duration::ZERO,
+            Bonus::Increment(increment) => increment,
+            Bonus::Delay(delay) => self.spent.min(delay),
+        };
+
+        if !self.is_flagged(player) {
+            *self.remaining_mut(player) += bonus;
+        }
+        self.spent = Duration::ZERO;
+    }
+
+    pub(crate) fn label(&self, player: Player) -> String {
+        let remaining = self.remaining(player);
+        let secs = remaining.as_secs();
+
+        // Tenths only matter once a flag is close.
+        if secs < 10 {
+            format!("{secs}.{}", remaining.subsec_millis() / 100)
+        } else {
+            format!("{}:{:02}", secs / 60, secs % 60)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::utils::player::Player;
+
+    use super::{Bonus, Clock, TimeControl};
+
+    fn clock(bonus: Bonus) -> Clock {
+        Clock::new(TimeControl {
+            base: Duration::from_secs(60),
+            bonus,
+        })
+    }
+
+    #[test]
+    fn increments_are_added_in_full() {
+        let mut clock = clock(Bonus::Increment(Duration::from_secs(2)));
+        clock.tick(Player::White, Duration::from_secs(5));
+        clock.press(Player::White);
+
+        assert_eq!(clock.remaining(Player::White), Duration::from_secs(57));
+        assert_eq!(clock.remaining(Player::Black), Duration::from_secs(60));
+        assert_eq!(clock.label(Player::White), "0:57");
+    }
+
+    #[test]
+    fn delays_only_give_back_the_time_used() {
+        let mut clock = clock(Bonus::Delay(Duration::from_secs(3)));
+        clock.tick(Player::White, Duration::from_secs(1));
+        clock.press(Player::White);
+        assert_eq!(clock.remaining(Player::White), Duration::from_secs(60));
+
+        clock.tick(Player::Black, Duration::from_secs(8));
+        clock.press(Player::Black);
+        assert_eq!(clock.remaining(Player::Black), Duration::from_secs(55));
+    }
+
+    #[test]
+    fn flags_fall_at_zero_and_stay_down() {
+        let mut clock = clock(Bonus::Increment(Duration::from_secs(2)));
+        clock.tick(Player::White, Duration::from_millis(59_500));
+        assert_eq!(clock.label(Player::White), "0.5");
+        assert!(!clock.is_flagged(Player::White));
+
+        clock.tick(Player::White, Duration::from_secs(1));
+        clock.press(Player::White);
+        assert!(clock.is_flagged(Player::White));
+    }
+}
@@ -0,0 +1,7 @@
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
+#[cfg_attr(feature = "tui", derive(clap::ValueEnum))]
+pub enum Controller {
+    #[default]
+    Human,
+    Ai,
+}
@@ -1,7 +1,4 @@
-use std::{
-    ops::{Add, Mul},
-    process::Output,
-};
+use std::ops::{Add, Mul};
 
 pub(crate) struct Delta<T> {
     pub(crate) x: T,
@@ -15,16 +12,14 @@ impl<T> From<(T, T)> for Delta<T> {
 }
 
 impl<T: Copy> Delta<T> {
+    #[cfg(feature = "tui")]
     pub(crate) fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
-
-    pub(crate) fn splat(val: T) -> Self {
-        Self { x: val, y: val }
-    }
 }
 
 impl<T> Delta<T> {
+    #[cfg(feature = "tui")]
     pub(crate) fn cast<F: Copy + 'static>(&self) -> Delta<F>
     where
         T: num::cast::AsPrimitive<F>,
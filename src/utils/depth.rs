@@ -1,123 +1,65 @@
 use anyhow::Result;
 
+use crate::unit::cell::Cell;
+
 use super::{file::File, range::RangeInc, rank::Rank};
 
-#[derive(PartialEq, Eq)]
-pub(crate) struct Depth(u8);
+/// How many rings the board has, counting the centre cell as the first.
+/// Files are lettered from `A` and ranks numbered from `1` on every board,
+/// so the centre cell moves outwards as the board grows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Depth(u8);
 
 impl Default for Depth {
     fn default() -> Self {
-        Self(6)
+        Self::GLINSKI
     }
 }
 
 impl Depth {
-    pub(crate) fn new(raw: u8) -> Result<Self> {
-        if (1..=6).contains(&raw) {
+    pub const GLINSKI: Self = Self(6);
+    pub const MAX: u8 = File::ALL.len().div_ceil(2) as u8;
+
+    pub fn new(raw: u8) -> Result<Self> {
+        if (1..=Self::MAX).contains(&raw) {
             Ok(Self(raw))
         } else {
-            anyhow::bail!("depth must be between 1 and 6; found: {}", raw)
+            anyhow::bail!("depth must be between 1 and {}; found: {}", Self::MAX, raw)
         }
     }
 
-    pub(crate) fn file_range(&self) -> RangeInc<File> {
-        match self.0 {
-            1 => RangeInc::new(File::FileF, File::FileF),
-            2 => RangeInc::new(File::FileE, File::FileG),
-            3 => RangeInc::new(File::FileD, File::FileH),
-            4 => RangeInc::new(File::FileC, File::FileI),
-            5 => RangeInc::new(File::FileB, File::FileK),
-            6 => RangeInc::new(File::FileA, File::FileL),
-            _ => unreachable!(),
-        }
+    /// Cells from the centre to the edge, not counting the centre itself.
+    pub fn radius(&self) -> isize {
+        isize::from(self.0) - 1
     }
 
-    pub(crate) fn rank_range(&self, file: File) -> RangeInc<Rank> {
-        match self.0 {
-            1 => match file {
-                File::FileF => RangeInc::new(Rank::Rank6, Rank::Rank6),
-                _ => unreachable!(),
-            },
-
-            2 => match file {
-                File::FileE => RangeInc::new(Rank::Rank5, Rank::Rank6),
-                File::FileF => RangeInc::new(Rank::Rank5, Rank::Rank7),
-                File::FileG => RangeInc::new(Rank::Rank5, Rank::Rank6),
-                _ => unreachable!(),
-            },
-
-            3 => match file {
-                File::FileD => RangeInc::new(Rank::Rank4, Rank::Rank6),
-                File::FileE => RangeInc::new(Rank::Rank4, Rank::Rank7),
-                File::FileF => RangeInc::new(Rank::Rank4, Rank::Rank8),
-                File::FileG => RangeInc::new(Rank::Rank4, Rank::Rank7),
-                File::FileH => RangeInc::new(Rank::Rank4, Rank::Rank6),
-                _ => unreachable!(),
-            },
-
-            4 => match file {
-                File::FileC => RangeInc::new(Rank::Rank3, Rank::Rank6),
-                File::FileD => RangeInc::new(Rank::Rank3, Rank::Rank7),
-                File::FileE => RangeInc::new(Rank::Rank3, Rank::Rank8),
-                File::FileF => RangeInc::new(Rank::Rank3, Rank::Rank9),
-                File::FileG => RangeInc::new(Rank::Rank3, Rank::Rank8),
-                File::FileH => RangeInc::new(Rank::Rank3, Rank::Rank7),
-                File::FileI => RangeInc::new(Rank::Rank3, Rank::Rank6),
-                _ => unreachable!(),
-            },
+    pub fn centre(&self) -> Cell {
+        let radius = self.radius();
+        Cell::new_in(
+            *self,
+            Rank::from_index(radius).unwrap(),
+            File::from_index(radius).unwrap(),
+        )
+    }
 
-            5 => match file {
-                File::FileB => RangeInc::new(Rank::Rank2, Rank::Rank6),
-                File::FileC => RangeInc::new(Rank::Rank2, Rank::Rank7),
-                File::FileD => RangeInc::new(Rank::Rank2, Rank::Rank8),
-                File::FileE => RangeInc::new(Rank::Rank2, Rank::Rank9),
-                File::FileF => RangeInc::new(Rank::Rank2, Rank::Rank10),
-                File::FileG => RangeInc::new(Rank::Rank2, Rank::Rank9),
-                File::FileH => RangeInc::new(Rank::Rank2, Rank::Rank8),
-                File::FileI => RangeInc::new(Rank::Rank2, Rank::Rank7),
-                File::FileK => RangeInc::new(Rank::Rank2, Rank::Rank6),
-                _ => unreachable!(),
-            },
+    pub(crate) fn file_range(&self) -> RangeInc<File> {
+        RangeInc::new(File::FileA, File::from_index(2 * self.radius()).unwrap())
+    }
 
-            6 => match file {
-                File::FileA => RangeInc::new(Rank::Rank1, Rank::Rank6),
-                File::FileB => RangeInc::new(Rank::Rank1, Rank::Rank7),
-                File::FileC => RangeInc::new(Rank::Rank1, Rank::Rank8),
-                File::FileD => RangeInc::new(Rank::Rank1, Rank::Rank9),
-                File::FileE => RangeInc::new(Rank::Rank1, Rank::Rank10),
-                File::FileF => RangeInc::new(Rank::Rank1, Rank::Rank11),
-                File::FileG => RangeInc::new(Rank::Rank1, Rank::Rank10),
-                File::FileH => RangeInc::new(Rank::Rank1, Rank::Rank9),
-                File::FileI => RangeInc::new(Rank::Rank1, Rank::Rank8),
-                File::FileK => RangeInc::new(Rank::Rank1, Rank::Rank7),
-                File::FileL => RangeInc::new(Rank::Rank1, Rank::Rank6),
-            },
+    pub(crate) fn rank_range(&self, file: File) -> RangeInc<Rank> {
+        let radius = self.radius();
+        let offset = (file.index() as isize - radius).abs();
 
-            _ => unreachable!(),
-        }
+        RangeInc::new(
+            Rank::Rank1,
+            Rank::from_index(2 * radius - offset).unwrap_or(Rank::Rank1),
+        )
     }
 
-    pub(crate) fn first_file(&self) -> File {
-        match self.0 {
-            1 => File::FileF,
-            2 => File::FileE,
-            3 => File::FileD,
-            4 => File::FileC,
-            5 => File::FileB,
-            6 => File::FileA,
-            _ => unreachable!(),
-        }
-    }
+    pub fn contains(&self, rank: Rank, file: File) -> bool {
+        let radius = self.radius();
+        let offset = (file.index() as isize - radius).abs();
 
-    pub(crate) fn first_rank(&self) -> Rank {
-        match self.0 {
-            1 => Rank::Rank6,
-            2 => Rank::Rank5,
-            3 => Rank::Rank4,
-            4 => Rank::Rank3,
-            5 => Rank::Rank2,
-            6 => Rank::Rank1,
-            _ => unreachable!(),
-        }
+        offset <= radius && rank.index() as isize <= 2 * radius - offset
     }
 }
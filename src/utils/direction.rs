@@ -1,5 +1,5 @@
-#[derive(Clone, Copy, Debug)]
-pub(crate) enum Direction {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
     Clock1,
     Clock2,
     Clock3,
@@ -15,7 +15,42 @@ pub(crate) enum Direction {
 }
 
 impl Direction {
-    pub(crate) fn reverse(&self) -> Direction {
+    pub const ALL: [Direction; 12] = [
+        Direction::Clock1,
+        Direction::Clock2,
+        Direction::Clock3,
+        Direction::Clock4,
+        Direction::Clock5,
+        Direction::Clock6,
+        Direction::Clock7,
+        Direction::Clock8,
+        Direction::Clock9,
+        Direction::Clock10,
+        Direction::Clock11,
+        Direction::Clock12,
+    ];
+
+    /// The directions a rook slides in, through the edges of its cell.
+    pub const ORTHOGONAL: [Direction; 6] = [
+        Direction::Clock2,
+        Direction::Clock4,
+        Direction::Clock6,
+        Direction::Clock8,
+        Direction::Clock10,
+        Direction::Clock12,
+    ];
+
+    /// The directions a bishop slides in, through the corners of its cell.
+    pub const DIAGONAL: [Direction; 6] = [
+        Direction::Clock1,
+        Direction::Clock3,
+        Direction::Clock5,
+        Direction::Clock7,
+        Direction::Clock9,
+        Direction::Clock11,
+    ];
+
+    pub fn reverse(&self) -> Direction {
         match self {
             Direction::Clock1 => Direction::Clock7,
             Direction::Clock3 => Direction::Clock9,
@@ -33,7 +68,7 @@ impl Direction {
         }
     }
 
-    pub(crate) fn turn_clockwise(&self) -> Direction {
+    pub fn turn_clockwise(&self) -> Direction {
         match self {
             Direction::Clock1 => Direction::Clock3,
             Direction::Clock3 => Direction::Clock5,
@@ -51,7 +86,7 @@ impl Direction {
         }
     }
 
-    pub(crate) fn turn_counter_clockwise(&self) -> Direction {
+    pub fn turn_counter_clockwise(&self) -> Direction {
         match self {
             Direction::Clock1 => Direction::Clock11,
             Direction::Clock3 => Direction::Clock1,
@@ -1,8 +1,8 @@
 use std::ops::Sub;
 
-use strum::EnumIter;
+use strum::{EnumIter, IntoEnumIterator};
 
-use super::{Step, range::RangeInc, rank::Rank};
+use super::Step;
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug, PartialOrd, EnumIter)]
 pub enum File {
@@ -18,6 +18,20 @@ pub enum File {
     FileI,
     FileK,
     FileL,
+    FileM,
+    FileN,
+    FileO,
+    FileP,
+    FileQ,
+    FileR,
+    FileS,
+    FileT,
+    FileU,
+    FileV,
+    FileW,
+    FileX,
+    FileY,
+    FileZ,
 }
 
 impl Sub for File {
@@ -29,68 +43,60 @@ impl Sub for File {
 
 impl Step for File {
     fn succ(&self) -> Option<Self> {
-        match self {
-            File::FileA => Some(File::FileB),
-            File::FileB => Some(File::FileC),
-            File::FileC => Some(File::FileD),
-            File::FileD => Some(File::FileE),
-            File::FileE => Some(File::FileF),
-            File::FileF => Some(File::FileG),
-            File::FileG => Some(File::FileH),
-            File::FileH => Some(File::FileI),
-            File::FileI => Some(File::FileK),
-            File::FileK => Some(File::FileL),
-            File::FileL => None,
-        }
-    }
-
-    fn pred(&self) -> Option<Self> {
-        match self {
-            File::FileA => None,
-            File::FileB => Some(File::FileA),
-            File::FileC => Some(File::FileB),
-            File::FileD => Some(File::FileC),
-            File::FileE => Some(File::FileD),
-            File::FileF => Some(File::FileE),
-            File::FileG => Some(File::FileF),
-            File::FileH => Some(File::FileG),
-            File::FileI => Some(File::FileH),
-            File::FileK => Some(File::FileI),
-            File::FileL => Some(File::FileK),
-        }
+        Self::from_index(self.index() as isize + 1)
     }
 }
 
 impl File {
-    pub(crate) fn label(&self) -> &'static str {
-        match self {
-            File::FileA => "A",
-            File::FileB => "B",
-            File::FileC => "C",
-            File::FileD => "D",
-            File::FileE => "E",
-            File::FileF => "F",
-            File::FileG => "G",
-            File::FileH => "H",
-            File::FileI => "I",
-            File::FileK => "K",
-            File::FileL => "L",
-        }
+    /// Every file letter in order. `J` is skipped, as on the Glinski board.
+    pub const ALL: [File; 25] = [
+        File::FileA,
+        File::FileB,
+        File::FileC,
+        File::FileD,
+        File::FileE,
+        File::FileF,
+        File::FileG,
+        File::FileH,
+        File::FileI,
+        File::FileK,
+        File::FileL,
+        File::FileM,
+        File::FileN,
+        File::FileO,
+        File::FileP,
+        File::FileQ,
+        File::FileR,
+        File::FileS,
+        File::FileT,
+        File::FileU,
+        File::FileV,
+        File::FileW,
+        File::FileX,
+        File::FileY,
+        File::FileZ,
+    ];
+
+    const LABELS: [&'static str; 25] = [
+        "A", "B", "C", "D", "E", "F", "G", "H", "I", "K", "L", "M", "N", "O", "P", "Q", "R", "S",
+        "T", "U", "V", "W", "X", "Y", "Z",
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn from_index(idx: isize) -> Option<Self> {
+        usize::try_from(idx)
+            .ok()
+            .and_then(|idx| Self::ALL.get(idx).copied())
+    }
+
+    pub fn label(&self) -> &'static str {
+        Self::LABELS[self.index()]
     }
 
-    pub(crate) fn rank_range(&self) -> RangeInc<Rank> {
-        match self {
-            File::FileA => RangeInc::new(Rank::Rank1, Rank::Rank6),
-            File::FileB => RangeInc::new(Rank::Rank1, Rank::Rank7),
-            File::FileC => RangeInc::new(Rank::Rank1, Rank::Rank8),
-            File::FileD => RangeInc::new(Rank::Rank1, Rank::Rank9),
-            File::FileE => RangeInc::new(Rank::Rank1, Rank::Rank10),
-            File::FileF => RangeInc::new(Rank::Rank1, Rank::Rank11),
-            File::FileG => RangeInc::new(Rank::Rank1, Rank::Rank10),
-            File::FileH => RangeInc::new(Rank::Rank1, Rank::Rank9),
-            File::FileI => RangeInc::new(Rank::Rank1, Rank::Rank8),
-            File::FileK => RangeInc::new(Rank::Rank1, Rank::Rank7),
-            File::FileL => RangeInc::new(Rank::Rank1, Rank::Rank6),
-        }
+    pub fn from_label(label: &str) -> Option<Self> {
+        File::iter().find(|file| file.label().eq_ignore_ascii_case(label))
     }
 }
@@ -1,7 +1,6 @@
-use clap::ValueEnum;
-
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Default, Debug)]
-pub(crate) enum FillMode {
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
+#[cfg_attr(feature = "tui", derive(clap::ValueEnum))]
+pub enum FillMode {
     #[default]
     Filled,
     Wireframe,
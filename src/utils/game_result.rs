@@ -0,0 +1,130 @@
+use crate::state::GameStatus;
+
+use super::{player::Player, scoring::ScoringRule};
+
+/// Points for each side, counted in quarters so Glinski's 3/4 point for a
+/// stalemate stays exact.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Score {
+    pub(crate) white: u8,
+    pub(crate) black: u8,
+}
+
+fn quarters_label(quarters: u8) -> &'static str {
+    match quarters {
+        0 => "0",
+        1 => "1/4",
+        2 => "1/2",
+        3 => "3/4",
+        _ => "1",
+    }
+}
+
+impl Score {
+    const LABELS: [&str; 5] = ["1-0", "0-1", "1/2-1/2", "3/4-1/4", "1/4-3/4"];
+
+    fn win(player: Player, quarters: u8) -> Self {
+        match player {
+            Player::White => Self {
+                white: quarters,
+                black: 4 - quarters,
+            },
+            Player::Black => Self {
+                white: 4 - quarters,
+                black: quarters,
+            },
+        }
+    }
+
+    pub(crate) fn label(self) -> String {
+        format!(
+            "{}-{}",
+            quarters_label(self.white),
+            quarters_label(self.black)
+        )
+    }
+
+    pub(crate) fn is_label(text: &str) -> bool {
+        Self::LABELS.contains(&text)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct GameResult {
+    pub(crate) winner: Option<Player>,
+    pub(crate) reason: GameStatus,
+    pub(crate) score: Score,
+}
+
+impl GameResult {
+    /// The result of a game that ended in `status` with `to_move` unable to
+    /// continue, or `None` while it is still being played.
+    pub(crate) fn new(status: GameStatus, to_move: Player, rule: ScoringRule) -> Option<Self> {
+        let winner = match status {
+            GameStatus::InProgress | GameStatus::Check => return None,
+            GameStatus::Checkmate | GameStatus::TimeForfeit => Some(to_move.toggle()),
+            GameStatus::Stalemate => match rule {
+                ScoringRule::Glinski => Some(to_move.toggle()),
+                ScoringRule::FideStyle => None,
+            },
+            GameStatus::InsufficientMaterial
+            | GameStatus::ThreefoldRepetition
+            | GameStatus::FiftyMoveRule => None,
+        };
+
+        let score = match (winner, status) {
+            (Some(winner), GameStatus::Stalemate) => Score::win(winner, 3),
+            (Some(winner), _) => Score::win(winner, 4),
+            (None, _) => Score { white: 2, black: 2 },
+        };
+
+        Some(Self {
+            winner,
+            reason: status,
+            score,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        state::GameStatus,
+        utils::{player::Player, scoring::ScoringRule},
+    };
+
+    use super::{GameResult, Score};
+
+    #[test]
+    fn stalemate_scores_depend_on_the_rule() {
+        let glinski =
+            GameResult::new(GameStatus::Stalemate, Player::Black, ScoringRule::Glinski).unwrap();
+        assert!(matches!(glinski.winner, Some(Player::White)));
+        assert_eq!(glinski.score.label(), "3/4-1/4");
+
+        let fide =
+            GameResult::new(GameStatus::Stalemate, Player::White, ScoringRule::FideStyle).unwrap();
+        assert!(fide.winner.is_none());
+        assert_eq!(fide.score.label(), "1/2-1/2");
+    }
+
+    #[test]
+    fn decisive_and_drawn_results() {
+        let mate =
+            GameResult::new(GameStatus::Checkmate, Player::White, ScoringRule::Glinski).unwrap();
+        assert!(matches!(mate.winner, Some(Player::Black)));
+        assert_eq!(mate.score.label(), "0-1");
+
+        let draw = GameResult::new(
+            GameStatus::ThreefoldRepetition,
+            Player::White,
+            ScoringRule::Glinski,
+        )
+        .unwrap();
+        assert_eq!(draw.score, Score { white: 2, black: 2 });
+
+        assert!(GameResult::new(GameStatus::Check, Player::White, ScoringRule::Glinski).is_none());
+        assert!(Score::is_label("1/4-3/4"));
+        assert!(!Score::is_label("e6"));
+    }
+}
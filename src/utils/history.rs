@@ -1,25 +1,199 @@
-use ratatui::text::Line;
+use crate::{board::UndoToken, pieces::PieceType, state::GameStatus, unit::cell::Cell};
 
-#[derive(Debug)]
-pub(crate) struct MoveRecord;
+use super::{
+    moves::{GeneralMoveType, MoveType, PawnMoveType},
+    player::Player,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MoveRecord {
+    pub(crate) piece: PieceType,
+    pub(crate) status: GameStatus,
+    pub(crate) undo: UndoToken,
+    pub(crate) position: u64,
+}
 
 impl MoveRecord {
-    pub(crate) fn line(&self) -> Line<'static> {
-        Line::from("...")
+    pub(crate) fn src(&self) -> Cell {
+        self.undo.src
+    }
+
+    pub(crate) fn dest(&self) -> Cell {
+        self.undo.mov.move_to
+    }
+
+    pub(crate) fn move_type(&self) -> MoveType {
+        self.undo.mov.move_type
+    }
+
+    pub(crate) fn captured(&self) -> Option<PieceType> {
+        self.undo.captured
+    }
+
+    pub(crate) fn label(&self) -> String {
+        let suffix = match self.status {
+            GameStatus::Check => "+",
+            GameStatus::Checkmate => "#",
+            _ => "",
+        };
+
+        if let MoveType::Rest(GeneralMoveType::Castle { wing, .. }) = self.move_type() {
+            return format!("{}{suffix}", wing.san());
+        }
+
+        let separator = if self.move_type().is_capture() {
+            "x"
+        } else {
+            "-"
+        };
+
+        let captured = match self.move_type() {
+            MoveType::Pawn(PawnMoveType::EnPassant { .. }) => "",
+            _ => self.captured().map_or("", PieceType::label),
+        };
+
+        let promotion = self
+            .move_type()
+            .promotion()
+            .map_or(String::new(), |promote_to| {
+                format!("={}", promote_to.label())
+            });
+
+        let en_passant = match self.move_type() {
+            MoveType::Pawn(PawnMoveType::EnPassant { .. }) => " EP",
+            _ => "",
+        };
+
+        format!(
+            "{}{}{}{}{}{}{}{}",
+            self.piece.label(),
+            self.src().label(),
+            separator,
+            captured,
+            self.dest().label(),
+            promotion,
+            en_passant,
+            suffix
+        )
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub(crate) struct History {
     records: Vec<MoveRecord>,
+    undone: Vec<MoveRecord>,
+    scroll: usize,
+    // The move number and side to move of the position the game started
+    // from, which the list is numbered on from.
+    fullmove_number: u32,
+    first_to_move: Player,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::starting_at(1, Player::White)
+    }
 }
 
 impl History {
-    pub(crate) fn lines(&self) -> Vec<Line<'static>> {
+    pub(crate) fn starting_at(fullmove_number: u32, first_to_move: Player) -> Self {
+        Self {
+            records: vec![],
+            undone: vec![],
+            scroll: 0,
+            fullmove_number,
+            first_to_move,
+        }
+    }
+
+    pub(crate) fn push(&mut self, record: MoveRecord) {
+        self.records.push(record);
+        self.scroll = 0;
+    }
+
+    pub(crate) fn undo(&mut self) -> Option<MoveRecord> {
+        let record = self.records.pop()?;
+        self.undone.push(record);
+        self.scroll = 0;
+
+        Some(record)
+    }
+
+    pub(crate) fn redo(&mut self) -> Option<MoveRecord> {
+        self.undone.pop()
+    }
+
+    /// Puts back a record taken by `redo` that could not be replayed.
+    pub(crate) fn unredo(&mut self, record: MoveRecord) {
+        self.undone.push(record);
+    }
+
+    pub(crate) fn clear_redo(&mut self) {
+        self.undone.clear();
+    }
+
+    pub(crate) fn records(&self) -> &[MoveRecord] {
+        &self.records
+    }
+
+    pub(crate) fn last_mut(&mut self) -> Option<&mut MoveRecord> {
+        self.records.last_mut()
+    }
+
+    pub(crate) fn lines(&self) -> Vec<String> {
         if self.records.is_empty() {
-            vec![Line::from("No moves")]
-        } else {
-            self.records.iter().map(MoveRecord::line).collect()
+            return vec!["No moves".to_string()];
+        }
+
+        let mut lines = vec![];
+        let mut number = self.fullmove_number;
+        let mut records = self.records.as_slice();
+
+        // A game that starts with Black to move opens on a line of its own.
+        if self.first_to_move == Player::Black
+            && let Some((first, rest)) = records.split_first()
+        {
+            lines.push(format!("{number:>3}... {}", first.label()));
+            number += 1;
+            records = rest;
         }
+
+        lines.extend(records.chunks(2).zip(number..).map(|(pair, number)| {
+            let moves = pair
+                .iter()
+                .map(MoveRecord::label)
+                .collect::<Vec<_>>()
+                .join("  ");
+
+            format!("{number:>3}. {moves}")
+        }));
+
+        lines
+    }
+
+    /// The lines that fit in `rows` rows of the panel, scrolled back from
+    /// the latest.
+    pub(crate) fn visible_lines(&self, rows: usize) -> Vec<String> {
+        let lines = self.lines();
+        let scroll = self.scroll.min(max_scroll(lines.len(), rows));
+        let end = lines.len() - scroll;
+        let start = end.saturating_sub(rows);
+
+        lines[start..end].to_vec()
+    }
+
+    /// Scrolls one line further back, stopping once the first line is in a
+    /// panel of `rows` rows.
+    pub(crate) fn scroll_up(&mut self, rows: usize) {
+        self.scroll = (self.scroll + 1).min(max_scroll(self.lines().len(), rows));
     }
+
+    pub(crate) fn scroll_down(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+}
+
+// Scrolled this far the first line is at the top of the panel.
+fn max_scroll(lines: usize, rows: usize) -> usize {
+    lines.saturating_sub(rows.max(1))
 }
@@ -0,0 +1,332 @@
+//! Key bindings for the board.
+//!
+//! The board's cells meet edge to edge at 12, 2, 4, 6, 8 and 10 o'clock, so
+//! the default keymap lays those six directions out on `q` `w` `e` / `a` `s`
+//! `d`, with `w` and `s` going straight up and down, which moves quitting to
+//! `Esc` or `Ctrl+C` and saving to `Ctrl+S`. The arrow keys keep their old
+//! meaning. The six diagonal jumps, through a corner to the next cell but
+//! one, have no keys unless a config file gives them some.
+//!
+//! A config file has one `action = key, key...` binding per line, with `#`
+//! starting a comment. Binding an action replaces its default keys:
+//!
+//! ```text
+//! quit = esc, ctrl+q
+//! save = ctrl+s
+//! clock-3 = shift+d
+//! ```
+
+use std::{collections::HashMap, fs, path::Path};
+
+use anyhow::{Context, Result, anyhow};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::direction::Direction;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Action {
+    Quit,
+    TogglePanel,
+    Save,
+    Select,
+    Undo,
+    Redo,
+    ScrollUp,
+    ScrollDown,
+    Command,
+    Flip,
+    Coordinates,
+    Move(Direction),
+}
+
+impl Action {
+    fn from_label(label: &str) -> Option<Self> {
+        let action = match label {
+            "quit" => Self::Quit,
+            "panel" => Self::TogglePanel,
+            "save" => Self::Save,
+            "select" => Self::Select,
+            "undo" => Self::Undo,
+            "redo" => Self::Redo,
+            "scroll-up" => Self::ScrollUp,
+            "scroll-down" => Self::ScrollDown,
+            "command" => Self::Command,
+            "flip" => Self::Flip,
+            "coordinates" => Self::Coordinates,
+            _ => {
+                let hour = label.strip_prefix("clock-")?.parse::<usize>().ok()?;
+                Self::Move(*Direction::ALL.get(hour.checked_sub(1)?)?)
+            }
+        };
+
+        Some(action)
+    }
+}
+
+/// A key together with the modifiers held down with it. Shift is folded
+/// into the character for letters, so `shift+d` and `D` are the same key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        match code {
+            KeyCode::Char(c) => Self {
+                code: KeyCode::Char(if modifiers.contains(KeyModifiers::SHIFT) {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }),
+                modifiers: modifiers - KeyModifiers::SHIFT,
+            },
+            _ => Self { code, modifiers },
+        }
+    }
+
+    fn plain(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+
+    fn parse(text: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+        while let Some((modifier, tail)) = rest.split_once('+').filter(|(_, tail)| !tail.is_empty())
+        {
+            modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(anyhow!("unknown modifier {modifier:?} in {text:?}")),
+            };
+            rest = tail;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "esc" => KeyCode::Esc,
+                "enter" => KeyCode::Enter,
+                "space" => KeyCode::Char(' '),
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                _ => return Err(anyhow!("unknown key {rest:?}")),
+            },
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    bindings: HashMap<Key, Action>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let char = |c| Key::plain(KeyCode::Char(c));
+        let ctrl = |c| Key::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+        let bindings = [
+            (Key::plain(KeyCode::Esc), Action::Quit),
+            (ctrl('c'), Action::Quit),
+            (char('p'), Action::TogglePanel),
+            (ctrl('s'), Action::Save),
+            (Key::plain(KeyCode::Enter), Action::Select),
+            (char(' '), Action::Select),
+            (char('u'), Action::Undo),
+            (char('r'), Action::Redo),
+            (Key::plain(KeyCode::PageUp), Action::ScrollUp),
+            (Key::plain(KeyCode::PageDown), Action::ScrollDown),
+            (char(':'), Action::Command),
+            (char('f'), Action::Flip),
+            (char('c'), Action::Coordinates),
+            (char('q'), Action::Move(Direction::Clock10)),
+            (char('w'), Action::Move(Direction::Clock12)),
+            (char('e'), Action::Move(Direction::Clock2)),
+            (char('d'), Action::Move(Direction::Clock4)),
+            (char('s'), Action::Move(Direction::Clock6)),
+            (char('a'), Action::Move(Direction::Clock8)),
+            (Key::plain(KeyCode::Left), Action::Move(Direction::Clock10)),
+            (Key::plain(KeyCode::Up), Action::Move(Direction::Clock12)),
+            (Key::plain(KeyCode::Right), Action::Move(Direction::Clock2)),
+            (Key::plain(KeyCode::Down), Action::Move(Direction::Clock6)),
+        ];
+
+        Self {
+            bindings: bindings.into_iter().collect(),
+        }
+    }
+}
+
+impl Keymap {
+    pub(crate) fn action(&self, key: KeyEvent) -> Option<Action> {
+        self.bindings
+            .get(&Key::new(key.code, key.modifiers))
+            .copied()
+    }
+
+    /// The default keymap with the bindings in `text` applied on top.
+    pub(crate) fn parse(text: &str) -> Result<Self> {
+        let mut keymap = Self::default();
+
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            keymap
+                .bind_line(line)
+                .with_context(|| format!("Invalid key binding on line {}", idx + 1))?;
+        }
+
+        Ok(keymap)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("Failed to load {}", path.display()))
+    }
+
+    fn bind_line(&mut self, line: &str) -> Result<()> {
+        let (label, keys) = line
+            .split_once('=')
+            .ok_or_else(|| anyhow!("expected `action = key`, found {line:?}"))?;
+        let label = label.trim();
+        let action =
+            Action::from_label(label).ok_or_else(|| anyhow!("unknown action {label:?}"))?;
+        let keys = keys
+            .split(',')
+            .map(|key| Key::parse(key.trim()))
+            .collect::<Result<Vec<_>>>()?;
+
+        self.bindings.retain(|_, bound| *bound != action);
+        for key in keys {
+            self.bindings.insert(key, action);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::utils::direction::Direction;
+
+    use super::{Action, Keymap};
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn default_keys_reach_all_six_neighbours() {
+        let keymap = Keymap::default();
+        let moves = "qweasd"
+            .chars()
+            .filter_map(|c| keymap.action(press(KeyCode::Char(c), KeyModifiers::NONE)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            moves,
+            [
+                Direction::Clock10,
+                Direction::Clock12,
+                Direction::Clock2,
+                Direction::Clock8,
+                Direction::Clock6,
+                Direction::Clock4,
+            ]
+            .map(Action::Move)
+        );
+        assert_eq!(
+            keymap.action(press(KeyCode::Char('s'), KeyModifiers::CONTROL)),
+            Some(Action::Save)
+        );
+        assert_eq!(
+            keymap.action(press(KeyCode::Left, KeyModifiers::NONE)),
+            Some(Action::Move(Direction::Clock10))
+        );
+    }
+
+    #[test]
+    fn config_bindings_replace_the_defaults() {
+        let keymap = Keymap::parse(
+            "# diagonals\nclock-3 = shift+d, L\nquit = q # like less\n\nundo = ctrl+z",
+        )
+        .unwrap();
+
+        assert_eq!(
+            keymap.action(press(KeyCode::Char('D'), KeyModifiers::SHIFT)),
+            Some(Action::Move(Direction::Clock3))
+        );
+        assert_eq!(
+            keymap.action(press(KeyCode::Char('l'), KeyModifiers::SHIFT)),
+            Some(Action::Move(Direction::Clock3))
+        );
+        assert_eq!(
+            keymap.action(press(KeyCode::Char('q'), KeyModifiers::NONE)),
+            Some(Action::Quit)
+        );
+        assert_eq!(keymap.action(press(KeyCode::Esc, KeyModifiers::NONE)), None);
+        assert_eq!(
+            keymap.action(press(KeyCode::Char('u'), KeyModifiers::NONE)),
+            None
+        );
+        assert_eq!(
+            keymap.action(press(KeyCode::Char('z'), KeyModifiers::CONTROL)),
+            Some(Action::Undo)
+        );
+    }
+
+    #[test]
+    fn the_old_quit_and_save_keys_can_be_bound_back() {
+        let keymap = Keymap::parse("quit = q\nsave = s").unwrap();
+
+        assert_eq!(
+            keymap.action(press(KeyCode::Char('q'), KeyModifiers::NONE)),
+            Some(Action::Quit)
+        );
+        assert_eq!(
+            keymap.action(press(KeyCode::Char('s'), KeyModifiers::NONE)),
+            Some(Action::Save)
+        );
+        assert_eq!(
+            keymap.action(press(KeyCode::Char('w'), KeyModifiers::NONE)),
+            Some(Action::Move(Direction::Clock12))
+        );
+    }
+
+    #[test]
+    fn bad_lines_are_reported_by_number() {
+        for (text, message) in [
+            ("quit = esc\nfly = f", "unknown action \"fly\""),
+            ("clock-13 = f", "unknown action \"clock-13\""),
+            ("\nsave = hyper+s", "unknown modifier \"hyper\""),
+            ("save = f13", "unknown key \"f13\""),
+            ("save", "expected `action = key`"),
+        ] {
+            let err = Keymap::parse(text).err().unwrap();
+            let line = text.lines().count();
+            assert!(
+                format!("{err:#}").contains(&format!("line {line}: {message}")),
+                "{err:#}"
+            );
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use ratatui::{
     style::Color,
-    widgets::canvas::{Circle, Line, Painter, Shape},
+    widgets::canvas::{Line, Painter, Shape},
 };
 
 use super::delta::Delta;
@@ -578,6 +578,34 @@ impl Shape for Mark<'C'> {
     }
 }
 
+impl Shape for Mark<'D'> {
+    fn draw(&self, painter: &mut Painter) {
+        let segment_n_ne = self.grid.segment_n_ne();
+        let segment_n_nw = self.grid.segment_n_nw();
+
+        let segment_s_se = self.grid.segment_s_se();
+        let segment_s_sw = self.grid.segment_s_sw();
+
+        let segment_center_n = self.grid.segment_center_n();
+        let segment_center_s = self.grid.segment_center_s();
+
+        let segment_e_ne = self.grid.segment_e_ne();
+        let segment_e_se = self.grid.segment_e_se();
+
+        segment_n_ne.draw(painter);
+        segment_n_nw.draw(painter);
+
+        segment_s_se.draw(painter);
+        segment_s_sw.draw(painter);
+
+        segment_center_n.draw(painter);
+        segment_center_s.draw(painter);
+
+        segment_e_ne.draw(painter);
+        segment_e_se.draw(painter);
+    }
+}
+
 impl Shape for Mark<'E'> {
     fn draw(&self, painter: &mut Painter) {
         let segment_n_ne = self.grid.segment_n_ne();
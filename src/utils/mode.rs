@@ -24,12 +24,4 @@ impl HighlightMode {
     pub(crate) fn new(current: bool, status: Status) -> Self {
         Self { current, status }
     }
-
-    pub(crate) fn set_current(&mut self, current: bool) {
-        self.current = current;
-    }
-
-    pub(crate) fn set_status(&mut self, status: Status) {
-        self.status = status;
-    }
 }
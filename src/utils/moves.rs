@@ -1,13 +1,11 @@
-use ratatui::style::Color;
+use crate::pieces::PieceType;
 
-use crate::{board::Board, pieces::Piece};
-
-use super::{Cell, direction::Direction};
+use super::{Cell, castling::Wing};
 
 #[derive(Clone, Copy, Debug)]
-pub(crate) struct Move {
-    pub(crate) move_to: Cell,
-    pub(crate) move_type: MoveType,
+pub struct Move {
+    pub move_to: Cell,
+    pub move_type: MoveType,
 }
 
 impl Move {
@@ -17,70 +15,56 @@ impl Move {
 }
 
 #[derive(Clone, Copy, Debug)]
-pub(crate) enum MoveType {
+pub enum MoveType {
     Rest(GeneralMoveType),
     Pawn(PawnMoveType),
 }
 
 impl MoveType {
-    pub(crate) fn is_promotion(self) -> bool {
+    pub fn is_promotion(self) -> bool {
+        self.promotion().is_some()
+    }
+
+    /// The piece a pawn making this move turns into.
+    pub fn promotion(self) -> Option<PieceType> {
+        match self {
+            Self::Pawn(
+                PawnMoveType::NonCapturePromotion { promote_to }
+                | PawnMoveType::CapturePromotion { promote_to },
+            ) => Some(promote_to),
+            _ => None,
+        }
+    }
+
+    pub fn is_capture(self) -> bool {
         matches!(
             self,
-            Self::Pawn(PawnMoveType::NonCapturePromotion | PawnMoveType::CapturePromotion)
+            Self::Rest(GeneralMoveType::Capture)
+                | Self::Pawn(
+                    PawnMoveType::NormalCapture
+                        | PawnMoveType::EnPassant { .. }
+                        | PawnMoveType::CapturePromotion { .. }
+                )
         )
     }
 }
 
 #[derive(Clone, Copy, Debug)]
-pub(crate) enum PawnMoveType {
+pub enum PawnMoveType {
     NonCapture,
     NormalCapture,
     EnPassant { remove_piece_on: Cell },
-    NonCapturePromotion,
-    CapturePromotion,
+    NonCapturePromotion { promote_to: PieceType },
+    CapturePromotion { promote_to: PieceType },
 }
 
 #[derive(Clone, Copy, Debug)]
-pub(crate) enum GeneralMoveType {
+pub enum GeneralMoveType {
     NonCapture,
     Capture,
-}
-
-pub(crate) fn single_direction_moves(
-    cell: Cell,
-    color: Color,
-    direction: Direction,
-    board: &Board,
-) -> Vec<Move> {
-    std::iter::successors(cell.next(direction), |current_cell: &Cell| {
-        current_cell.next(direction)
-    })
-    .fold(
-        (vec![], false),
-        |(mut moves_in_curr_direction, encountered): (Vec<Move>, _), cell| {
-            if encountered {
-                (moves_in_curr_direction, encountered)
-            } else {
-                let encountered = match board[cell].occupant() {
-                    Some(piece) => {
-                        if piece.color() != color {
-                            moves_in_curr_direction
-                                .push(Move::new(cell, MoveType::Rest(GeneralMoveType::Capture)));
-                        }
-
-                        true
-                    }
-                    None => {
-                        moves_in_curr_direction
-                            .push(Move::new(cell, MoveType::Rest(GeneralMoveType::NonCapture)));
-
-                        false
-                    }
-                };
-
-                (moves_in_curr_direction, encountered)
-            }
-        },
-    )
-    .0
+    Castle {
+        wing: Wing,
+        rook_from: Cell,
+        rook_to: Cell,
+    },
 }
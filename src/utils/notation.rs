@@ -0,0 +1,536 @@
+use anyhow::{Result, anyhow, bail};
+
+use crate::{
+    board::{Board, cell_label},
+    pieces::PieceType,
+    state::GameStatus,
+    unit::cell::Cell,
+};
+
+use super::{
+    castling::Wing,
+    depth::Depth,
+    file::File,
+    moves::{GeneralMoveType, Move, MoveType},
+    player::Player,
+    rank::Rank,
+};
+
+fn file_label(file: File) -> String {
+    file.label().to_ascii_lowercase()
+}
+
+fn disambiguation(board: &mut Board, src: Cell, dest: Cell) -> String {
+    let Some((color, piece_type)) = board[src]
+        .occupant()
+        .map(|piece| (piece.color(), piece.ty()))
+    else {
+        return String::new();
+    };
+
+    let rivals = board
+        .occupied_cells(color)
+        .into_iter()
+        .filter(|cell| {
+            *cell != src
+                && board[*cell]
+                    .occupant()
+                    .is_some_and(|piece| piece.ty() == piece_type)
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .filter(|cell| {
+            board
+                .legal_moves(*cell)
+                .iter()
+                .any(|mov| mov.move_to == dest)
+        })
+        .collect::<Vec<_>>();
+
+    if rivals.is_empty() {
+        String::new()
+    } else if rivals.iter().all(|cell| cell.file != src.file) {
+        file_label(src.file)
+    } else if rivals.iter().all(|cell| cell.rank != src.rank) {
+        src.rank.label().to_string()
+    } else {
+        cell_label(src)
+    }
+}
+
+pub(crate) fn to_san(board: &mut Board, src: Cell, mov: Move) -> String {
+    let Some((color, piece_type)) = board[src]
+        .occupant()
+        .map(|piece| (piece.color(), piece.ty()))
+    else {
+        return String::new();
+    };
+
+    let capture = if mov.move_type.is_capture() { "x" } else { "" };
+
+    let mut san = if let MoveType::Rest(GeneralMoveType::Castle { wing, .. }) = mov.move_type {
+        wing.san().to_string()
+    } else {
+        let mut san = match piece_type {
+            PieceType::Pawn if mov.move_type.is_capture() => format!("{}x", file_label(src.file)),
+            PieceType::Pawn => String::new(),
+            _ => format!(
+                "{}{}{}",
+                piece_type.label(),
+                disambiguation(board, src, mov.move_to),
+                capture
+            ),
+        };
+
+        san.push_str(&cell_label(mov.move_to));
+        san
+    };
+
+    if let Some(promote_to) = mov.move_type.promotion() {
+        san.push('=');
+        san.push_str(promote_to.label());
+    }
+
+    let status = board.with_move_applied(src, mov, |board| {
+        GameStatus::evaluate(board, color.toggle())
+    });
+
+    match status {
+        GameStatus::Check => san.push('+'),
+        GameStatus::Checkmate => san.push('#'),
+        _ => {}
+    }
+
+    san
+}
+
+enum Disambiguation {
+    None,
+    File(File),
+    Rank(Rank),
+    Cell(Cell),
+}
+
+impl Disambiguation {
+    fn parse(text: &str, depth: Depth) -> Result<Self> {
+        if text.is_empty() {
+            return Ok(Self::None);
+        }
+
+        if let Ok(cell) = Cell::from_label_in(depth, text) {
+            return Ok(Self::Cell(cell));
+        }
+
+        if let Some(rank) = Rank::from_label(text) {
+            return Ok(Self::Rank(rank));
+        }
+
+        File::from_label(text)
+            .filter(|_| text.chars().all(|c| c.is_ascii_lowercase()))
+            .map(Self::File)
+            .ok_or_else(|| anyhow!("Invalid disambiguation: {text:?}"))
+    }
+
+    fn matches(&self, cell: Cell) -> bool {
+        match self {
+            Self::None => true,
+            Self::File(file) => cell.file == *file,
+            Self::Rank(rank) => cell.rank == *rank,
+            Self::Cell(expected) => cell == *expected,
+        }
+    }
+}
+
+pub(crate) fn parse_san(board: &mut Board, color: Player, san: &str) -> Result<(Cell, Move)> {
+    let text = san.trim().trim_end_matches(['+', '#', '!', '?']);
+
+    if let Some(wing) = [Wing::King, Wing::Queen]
+        .into_iter()
+        .find(|wing| text.replace('0', "O") == wing.san())
+    {
+        return board
+            .occupied_cells(color)
+            .into_iter()
+            .filter(|cell| {
+                board[*cell]
+                    .occupant()
+                    .is_some_and(|piece| piece.ty() == PieceType::King)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .find_map(|cell| {
+                board
+                    .legal_moves(cell)
+                    .into_iter()
+                    .find_map(|mov| match mov.move_type {
+                        MoveType::Rest(GeneralMoveType::Castle { wing: castled, .. })
+                            if castled == wing =>
+                        {
+                            Some((cell, mov))
+                        }
+                        _ => None,
+                    })
+            })
+            .ok_or_else(|| anyhow!("No legal move matches {san:?}"));
+    }
+
+    let (text, promotion) = match text.split_once('=') {
+        Some((body, piece)) => (body, Some(parse_promotion(piece, san)?)),
+        None => (text, None),
+    };
+
+    let (piece_type, text) = match text
+        .chars()
+        .next()
+        .and_then(|letter| PieceType::from_label(&letter.to_string()))
+    {
+        Some(piece_type) => (piece_type, &text[1..]),
+        None => (PieceType::Pawn, text),
+    };
+
+    // The destination is the rank's digits and the one letter before them.
+    let dest_start = text
+        .trim_end_matches(|c: char| c.is_ascii_digit())
+        .char_indices()
+        .next_back()
+        .map(|(idx, _)| idx)
+        .ok_or_else(|| anyhow!("Missing destination cell in {san:?}"))?;
+    let dest = Cell::from_label_in(board.depth(), &text[dest_start..])
+        .map_err(|_| anyhow!("Invalid destination cell in {san:?}"))?;
+
+    let prefix = &text[..dest_start];
+    let (prefix, capture) = match prefix.strip_suffix('x') {
+        Some(prefix) => (prefix, true),
+        None => (prefix.strip_suffix('-').unwrap_or(prefix), false),
+    };
+    let disambiguation = Disambiguation::parse(prefix, board.depth())?;
+
+    let candidates = board
+        .occupied_cells(color)
+        .into_iter()
+        .filter(|cell| {
+            disambiguation.matches(*cell)
+                && board[*cell]
+                    .occupant()
+                    .is_some_and(|piece| piece.ty() == piece_type)
+        })
+        .collect::<Vec<_>>();
+
+    let mut matches = candidates.into_iter().filter_map(|cell| {
+        find_move(board.legal_moves(cell), dest, promotion).map(|mov| (cell, mov))
+    });
+
+    let Some((src, mov)) = matches.next() else {
+        bail!("No legal move matches {san:?}");
+    };
+
+    if matches.next().is_some() {
+        bail!("Ambiguous move {san:?}");
+    }
+
+    if capture && !mov.move_type.is_capture() {
+        bail!("{san:?} is marked as a capture but captures nothing");
+    }
+
+    if promotion.is_some() && !mov.move_type.is_promotion() {
+        bail!("{san:?} is not a promotion");
+    }
+
+    Ok((src, mov))
+}
+
+/// Parses a move typed either as a source and destination cell, like
+/// `f5-f6`, `f5xe5` or `e9e10=N`, or in algebraic notation.
+pub(crate) fn parse_move(board: &mut Board, color: Player, text: &str) -> Result<(Cell, Move)> {
+    match split_coordinates(text.trim(), board.depth()) {
+        Some((src, dest, promotion)) => {
+            let promotion = match promotion {
+                Some(piece) => Some(parse_promotion(piece, text)?),
+                None => None,
+            };
+
+            if board[src]
+                .occupant()
+                .is_none_or(|piece| piece.color() != color)
+            {
+                bail!("{} has none of your pieces on it", cell_label(src));
+            }
+
+            let mov = find_move(board.legal_moves(src), dest, promotion)
+                .ok_or_else(|| anyhow!("{text:?} is not a legal move"))?;
+
+            if promotion.is_some() && !mov.move_type.is_promotion() {
+                bail!("{text:?} is not a promotion");
+            }
+
+            Ok((src, mov))
+        }
+        None => parse_san(board, color, text),
+    }
+}
+
+/// The move to `dest` among `moves`, promoting to `promotion` or, if none
+/// was given, a queen.
+fn find_move(moves: Vec<Move>, dest: Cell, promotion: Option<PieceType>) -> Option<Move> {
+    let promotion = promotion.unwrap_or(PieceType::Queen);
+
+    moves.into_iter().find(|mov| {
+        mov.move_to == dest
+            && mov
+                .move_type
+                .promotion()
+                .is_none_or(|promote_to| promote_to == promotion)
+    })
+}
+
+fn split_coordinates(text: &str, depth: Depth) -> Option<(Cell, Cell, Option<&str>)> {
+    let (text, promotion) = match text.split_once('=') {
+        Some((body, piece)) => (body, Some(piece)),
+        None => (text, None),
+    };
+
+    // The source cell ends at the first letter after a digit.
+    let split = text
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| !c.is_ascii_digit())
+        .map(|(idx, _)| idx)?;
+    let (src, dest) = text.split_at(split);
+    let dest = dest
+        .strip_prefix(['-', 'x'])
+        .filter(|dest| dest.starts_with(|c: char| c.is_ascii_alphabetic()))
+        .unwrap_or(dest);
+
+    Some((
+        Cell::from_label_in(depth, src).ok()?,
+        Cell::from_label_in(depth, dest).ok()?,
+        promotion,
+    ))
+}
+
+fn parse_promotion(piece: &str, text: &str) -> Result<PieceType> {
+    match piece.chars().collect::<Vec<_>>().as_slice() {
+        [letter] => PieceType::from_label(&letter.to_string())
+            .filter(|piece| PieceType::PROMOTIONS.contains(piece))
+            .ok_or_else(|| anyhow!("Invalid promotion piece in {text:?}")),
+        _ => bail!("Invalid promotion piece in {text:?}"),
+    }
+}
+
+/// Every legal move for `color`, both in algebraic notation and as a pair of
+/// cells, with each promotion choice spelled out.
+pub(crate) fn legal_move_labels(board: &mut Board, color: Player) -> Vec<String> {
+    let mut labels = vec![];
+
+    for src in board.occupied_cells(color) {
+        for mov in board.legal_moves(src) {
+            let mut coordinates = format!("{}-{}", cell_label(src), cell_label(mov.move_to));
+            if let Some(promote_to) = mov.move_type.promotion() {
+                coordinates.push('=');
+                coordinates.push_str(promote_to.label());
+            }
+
+            labels.push(to_san(board, src, mov));
+            labels.push(coordinates);
+        }
+    }
+
+    labels.sort();
+    labels.dedup();
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        board::{Board, board_set},
+        pieces::PieceType,
+        unit::cell::Cell,
+        utils::{file::File, player::Player, rank::Rank},
+    };
+
+    use super::{legal_move_labels, parse_move, parse_san, to_san};
+
+    fn san_for(board: &mut Board, src: Cell, dest: Cell, promotion: Option<PieceType>) -> String {
+        let mov = board
+            .legal_moves(src)
+            .into_iter()
+            .find(|mov| mov.move_to == dest && mov.move_type.promotion() == promotion)
+            .expect("move should be legal");
+
+        to_san(board, src, mov)
+    }
+
+    #[test]
+    fn every_opening_move_round_trips_through_san() {
+        let mut board = Board::new();
+
+        for src in board.occupied_cells(Player::White) {
+            for mov in board.legal_moves(src) {
+                let san = to_san(&mut board, src, mov);
+                let (parsed_src, parsed_mov) = parse_san(&mut board, Player::White, &san)
+                    .unwrap_or_else(|err| panic!("{san}: {err}"));
+
+                assert_eq!(parsed_src, src, "{san}");
+                assert_eq!(parsed_mov.move_to, mov.move_to, "{san}");
+            }
+        }
+    }
+
+    #[test]
+    fn pawn_moves_use_file_for_captures_and_suffix_promotions() {
+        let mut board = board_set!(
+            (Player::White, pawn, Pawn) on [Rank::Rank5, File::FileF],
+            (Player::White, pawn, Pawn) on [Rank::Rank9, File::FileE],
+            (Player::Black, pawn, Pawn) on [Rank::Rank5, File::FileE],
+            (Player::White, king, King) on [Rank::Rank1, File::FileA],
+            (Player::Black, king, King) on [Rank::Rank7, File::FileK],
+        );
+
+        let capture = Cell::new(Rank::Rank5, File::FileE);
+        let src = Cell::new(Rank::Rank5, File::FileF);
+        assert_eq!(san_for(&mut board, src, capture, None), "fxe5");
+
+        let src = Cell::new(Rank::Rank9, File::FileE);
+        let dest = Cell::new(Rank::Rank10, File::FileE);
+        assert_eq!(
+            san_for(&mut board, src, dest, Some(PieceType::Knight)),
+            "e10=N"
+        );
+
+        let (parsed_src, parsed_mov) = parse_san(&mut board, Player::White, "e10=N").unwrap();
+        assert_eq!(parsed_src, src);
+        assert_eq!(parsed_mov.move_to, dest);
+        assert_eq!(parsed_mov.move_type.promotion(), Some(PieceType::Knight));
+    }
+
+    #[test]
+    fn rivals_reaching_the_same_cell_are_disambiguated() {
+        let mut board = board_set!(
+            (Player::White, rook, Rook) on [Rank::Rank3, File::FileD],
+            (Player::White, rook, Rook) on [Rank::Rank3, File::FileF],
+            (Player::White, rook, Rook) on [Rank::Rank9, File::FileF],
+            (Player::White, king, King) on [Rank::Rank1, File::FileA],
+            (Player::Black, king, King) on [Rank::Rank7, File::FileK],
+        );
+
+        let dest = Cell::new(Rank::Rank5, File::FileF);
+        let src = Cell::new(Rank::Rank3, File::FileD);
+        assert_eq!(san_for(&mut board, src, dest, None), "Rdf5");
+
+        let src = Cell::new(Rank::Rank9, File::FileF);
+        assert_eq!(san_for(&mut board, src, dest, None), "R9f5");
+
+        let src = Cell::new(Rank::Rank3, File::FileF);
+        assert_eq!(san_for(&mut board, src, dest, None), "Rf3f5");
+
+        assert!(parse_san(&mut board, Player::White, "Rf5").is_err());
+        assert!(parse_san(&mut board, Player::White, "Rff5").is_err());
+
+        let (parsed_src, _) = parse_san(&mut board, Player::White, "Rf3-f5").unwrap();
+        assert_eq!(parsed_src, src);
+        let (parsed_src, _) = parse_san(&mut board, Player::White, "R9f5").unwrap();
+        assert_eq!(parsed_src, Cell::new(Rank::Rank9, File::FileF));
+    }
+
+    #[test]
+    fn checks_and_mates_are_suffixed() {
+        let mut board = board_set!(
+            (Player::White, rook, Rook) on [Rank::Rank3, File::FileE],
+            (Player::White, king, King) on [Rank::Rank1, File::FileA],
+            (Player::Black, king, King) on [Rank::Rank11, File::FileF],
+        );
+
+        let src = Cell::new(Rank::Rank3, File::FileE);
+        let dest = Cell::new(Rank::Rank3, File::FileF);
+        assert_eq!(san_for(&mut board, src, dest, None), "Rf3+");
+    }
+
+    #[test]
+    fn illegal_or_malformed_moves_are_rejected() {
+        let mut board = Board::new();
+
+        assert!(parse_san(&mut board, Player::White, "Qe9").is_err());
+        assert!(parse_san(&mut board, Player::White, "z4").is_err());
+        assert!(parse_san(&mut board, Player::White, "xf6").is_err());
+        assert!(parse_san(&mut board, Player::White, "f6=Q").is_err());
+        assert!(parse_san(&mut board, Player::White, "f6").is_ok());
+    }
+
+    #[test]
+    fn non_ascii_text_is_rejected_without_panicking() {
+        let mut board = Board::new();
+
+        for text in ["é5", "Né5", "éxf6", "fé6", "f6=Ö", "ü", "e4-é5"] {
+            assert!(
+                parse_san(&mut board, Player::White, text).is_err(),
+                "{text}"
+            );
+            assert!(
+                parse_move(&mut board, Player::White, text).is_err(),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn typed_moves_take_cells_or_algebraic_notation() {
+        let mut board = board_set!(
+            (Player::White, pawn, Pawn) on [Rank::Rank9, File::FileE],
+            (Player::White, rook, Rook) on [Rank::Rank3, File::FileE],
+            (Player::White, king, King) on [Rank::Rank1, File::FileA],
+            (Player::Black, king, King) on [Rank::Rank7, File::FileK],
+        );
+        let e3 = Cell::new(Rank::Rank3, File::FileE);
+        let e9 = Cell::new(Rank::Rank9, File::FileE);
+
+        for text in ["e3-e5", "e3e5", "E3-E5", "Re5"] {
+            let (src, mov) = parse_move(&mut board, Player::White, text).unwrap();
+            assert_eq!(
+                (src, mov.move_to, mov.move_type.promotion()),
+                (e3, Cell::new(Rank::Rank5, File::FileE), None)
+            );
+        }
+
+        let (src, mov) = parse_move(&mut board, Player::White, "e9-e10=N").unwrap();
+        assert_eq!(
+            (src, mov.move_type.promotion()),
+            (e9, Some(PieceType::Knight))
+        );
+        let (_, mov) = parse_move(&mut board, Player::White, "e9e10").unwrap();
+        assert_eq!(mov.move_type.promotion(), Some(PieceType::Queen));
+
+        for (text, message) in [
+            ("e4-e5", "e4 has none of your pieces"),
+            ("k7-k6", "k7 has none of your pieces"),
+            ("e3-f5", "\"e3-f5\" is not a legal move"),
+            ("e3-e5=Q", "is not a promotion"),
+            ("e9-e10=K", "Invalid promotion piece"),
+        ] {
+            let err = parse_move(&mut board, Player::White, text).err().unwrap();
+            assert!(err.to_string().contains(message), "{text}: {err}");
+        }
+    }
+
+    #[test]
+    fn completions_list_both_forms_of_every_legal_move() {
+        let mut board = board_set!(
+            (Player::White, pawn, Pawn) on [Rank::Rank9, File::FileE],
+            (Player::White, king, King) on [Rank::Rank1, File::FileA],
+            (Player::Black, king, King) on [Rank::Rank7, File::FileK],
+        );
+
+        let labels = legal_move_labels(&mut board, Player::White);
+        for label in ["e10=N", "e9-e10=N", "e10=Q", "e9-e10=Q", "Kb1", "a1-b1"] {
+            assert!(
+                labels.iter().any(|other| other == label),
+                "{label}: {labels:?}"
+            );
+        }
+        assert!(!labels.iter().any(|label| label == "e10"), "{labels:?}");
+        assert_eq!(
+            labels.len(),
+            2 * (4 + board.legal_moves(Cell::new(Rank::Rank1, File::FileA)).len())
+        );
+    }
+}
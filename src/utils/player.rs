@@ -1,28 +1,20 @@
-use ratatui::style::Color;
-
-#[derive(Clone, Copy, Debug, Default)]
-pub(crate) enum Player {
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "tui", derive(clap::ValueEnum))]
+pub enum Player {
     #[default]
     White,
     Black,
 }
 
 impl Player {
-    pub(crate) fn color(&self) -> Color {
-        match self {
-            Self::White => Color::White,
-            Self::Black => Color::Black,
-        }
-    }
-
-    pub(crate) fn toggle(&self) -> Player {
+    pub fn toggle(&self) -> Player {
         match self {
             Self::White => Self::Black,
             Self::Black => Self::White,
         }
     }
 
-    pub(crate) fn label(&self) -> &'static str {
+    pub fn label(&self) -> &'static str {
         match self {
             Self::White => "W",
             Self::Black => "B",
@@ -1,23 +1,13 @@
-use ratatui::text::Line;
-
 use crate::unit::cell::Cell;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq)]
 pub(crate) enum MoveProgression {
     #[default]
     Navigation,
     PossiblyMoving(Cell),
+    /// A pawn on `from` is waiting to learn what it promotes to on `at`.
     Promoting {
+        from: Cell,
         at: Cell,
     },
 }
-
-impl MoveProgression {
-    pub(crate) fn line(&self) -> Line<'static> {
-        match self {
-            Self::Navigation => Line::from("M: NAV"),
-            Self::PossiblyMoving(cell) => Line::from(format!("M: SEL {}", cell.label())),
-            Self::Promoting { at } => Line::from(format!("M: PRM {}", at.label())),
-        }
-    }
-}
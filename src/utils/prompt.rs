@@ -0,0 +1,172 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+/// The `:` command line for typing moves.
+#[derive(Debug, Default)]
+pub(crate) struct Prompt {
+    input: String,
+    error: Option<String>,
+    // Completions offered by the last Tab, and which one is shown.
+    completions: Vec<String>,
+    shown: Option<usize>,
+}
+
+impl Prompt {
+    pub(crate) fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Adds `c` to the input. Moves are written in ASCII, so anything else
+    /// is left out.
+    pub(crate) fn push(&mut self, c: char) {
+        if !c.is_ascii() {
+            return;
+        }
+
+        self.input.push(c);
+        self.edited();
+    }
+
+    pub(crate) fn pop(&mut self) {
+        self.input.pop();
+        self.edited();
+    }
+
+    pub(crate) fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    fn edited(&mut self) {
+        self.error = None;
+        self.completions.clear();
+        self.shown = None;
+    }
+
+    /// Completes the input from `candidates`. A unique match is filled in;
+    /// otherwise the input grows to what the matches share, and pressing Tab
+    /// again steps through them.
+    pub(crate) fn complete(&mut self, candidates: impl FnOnce() -> Vec<String>) {
+        if !self.completions.is_empty() {
+            let next = self
+                .shown
+                .map_or(0, |idx| (idx + 1) % self.completions.len());
+            self.input = self.completions[next].clone();
+            self.shown = Some(next);
+            return;
+        }
+
+        let input = self.input.to_ascii_lowercase();
+        let matches = candidates()
+            .into_iter()
+            .filter(|candidate| candidate.to_ascii_lowercase().starts_with(&input))
+            .collect::<Vec<_>>();
+
+        match matches.as_slice() {
+            [] => self.error = Some(format!("No legal move starts with {:?}", self.input)),
+            [only] => {
+                self.input = only.clone();
+                self.error = None;
+            }
+            [first, rest @ ..] => {
+                let shared = rest.iter().fold(first.len(), |len, other| {
+                    first
+                        .bytes()
+                        .zip(other.bytes())
+                        .take(len)
+                        .take_while(|(lhs, rhs)| lhs == rhs)
+                        .count()
+                });
+                if shared > self.input.len() {
+                    self.input = first[..shared].to_string();
+                }
+                self.error = None;
+                self.completions = matches;
+            }
+        }
+    }
+}
+
+impl Widget for &Prompt {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let mut spans = vec![Span::from(":"), Span::from(self.input.as_str())];
+        if let Some(error) = &self.error {
+            spans.push(Span::from(format!("  {error}")).fg(Color::Red));
+        } else if self.completions.len() > 1 {
+            spans.push(Span::from(format!("  {}", self.completions.join(" "))).fg(Color::DarkGray));
+        }
+
+        Line::from(spans).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Prompt;
+
+    fn candidates() -> Vec<String> {
+        ["Nc4", "Nd3", "e5", "e6", "e4-e5", "e4-e6"]
+            .map(String::from)
+            .to_vec()
+    }
+
+    fn typed(text: &str) -> Prompt {
+        let mut prompt = Prompt::default();
+        text.chars().for_each(|c| prompt.push(c));
+        prompt
+    }
+
+    #[test]
+    fn unique_matches_are_filled_in() {
+        let mut prompt = typed("nd");
+        prompt.complete(candidates);
+        assert_eq!(prompt.input(), "Nd3");
+    }
+
+    #[test]
+    fn shared_prefixes_grow_then_tab_cycles() {
+        let mut prompt = typed("e4");
+        prompt.complete(candidates);
+        assert_eq!(prompt.input(), "e4-e");
+
+        prompt.complete(candidates);
+        assert_eq!(prompt.input(), "e4-e5");
+        prompt.complete(candidates);
+        assert_eq!(prompt.input(), "e4-e6");
+        prompt.complete(candidates);
+        assert_eq!(prompt.input(), "e4-e5");
+
+        prompt.pop();
+        prompt.push('6');
+        assert_eq!(prompt.input(), "e4-e6");
+        assert!(prompt.completions.is_empty());
+    }
+
+    #[test]
+    fn only_ascii_is_typed() {
+        let mut prompt = typed("Né5ü");
+        assert_eq!(prompt.input(), "N5");
+
+        prompt.complete(candidates);
+        assert_eq!(prompt.input(), "N5");
+        assert!(prompt.error.is_some());
+    }
+
+    #[test]
+    fn missing_matches_are_reported_until_the_next_edit() {
+        let mut prompt = typed("q");
+        prompt.complete(candidates);
+        assert_eq!(prompt.input(), "q");
+        assert!(prompt.error.is_some());
+
+        prompt.pop();
+        assert!(prompt.error.is_none());
+    }
+}
@@ -16,6 +16,20 @@ pub enum Rank {
     Rank9,
     Rank10,
     Rank11,
+    Rank12,
+    Rank13,
+    Rank14,
+    Rank15,
+    Rank16,
+    Rank17,
+    Rank18,
+    Rank19,
+    Rank20,
+    Rank21,
+    Rank22,
+    Rank23,
+    Rank24,
+    Rank25,
 }
 
 impl Sub for Rank {
@@ -27,51 +41,59 @@ impl Sub for Rank {
 
 impl Step for Rank {
     fn succ(&self) -> Option<Self> {
-        match self {
-            Rank::Rank1 => Some(Rank::Rank2),
-            Rank::Rank2 => Some(Rank::Rank3),
-            Rank::Rank3 => Some(Rank::Rank4),
-            Rank::Rank4 => Some(Rank::Rank5),
-            Rank::Rank5 => Some(Rank::Rank6),
-            Rank::Rank6 => Some(Rank::Rank7),
-            Rank::Rank7 => Some(Rank::Rank8),
-            Rank::Rank8 => Some(Rank::Rank9),
-            Rank::Rank9 => Some(Rank::Rank10),
-            Rank::Rank10 => Some(Rank::Rank11),
-            Rank::Rank11 => None,
-        }
-    }
-    fn pred(&self) -> Option<Self> {
-        match self {
-            Rank::Rank1 => None,
-            Rank::Rank2 => Some(Rank::Rank1),
-            Rank::Rank3 => Some(Rank::Rank2),
-            Rank::Rank4 => Some(Rank::Rank3),
-            Rank::Rank5 => Some(Rank::Rank4),
-            Rank::Rank6 => Some(Rank::Rank5),
-            Rank::Rank7 => Some(Rank::Rank6),
-            Rank::Rank8 => Some(Rank::Rank7),
-            Rank::Rank9 => Some(Rank::Rank8),
-            Rank::Rank10 => Some(Rank::Rank9),
-            Rank::Rank11 => Some(Rank::Rank10),
-        }
+        Self::from_index(self.index() as isize + 1)
     }
 }
 
 impl Rank {
-    pub(crate) fn label(&self) -> &'static str {
-        match self {
-            Rank::Rank1 => "1",
-            Rank::Rank2 => "2",
-            Rank::Rank3 => "3",
-            Rank::Rank4 => "4",
-            Rank::Rank5 => "5",
-            Rank::Rank6 => "6",
-            Rank::Rank7 => "7",
-            Rank::Rank8 => "8",
-            Rank::Rank9 => "9",
-            Rank::Rank10 => "10",
-            Rank::Rank11 => "11",
-        }
+    pub const ALL: [Rank; 25] = [
+        Rank::Rank1,
+        Rank::Rank2,
+        Rank::Rank3,
+        Rank::Rank4,
+        Rank::Rank5,
+        Rank::Rank6,
+        Rank::Rank7,
+        Rank::Rank8,
+        Rank::Rank9,
+        Rank::Rank10,
+        Rank::Rank11,
+        Rank::Rank12,
+        Rank::Rank13,
+        Rank::Rank14,
+        Rank::Rank15,
+        Rank::Rank16,
+        Rank::Rank17,
+        Rank::Rank18,
+        Rank::Rank19,
+        Rank::Rank20,
+        Rank::Rank21,
+        Rank::Rank22,
+        Rank::Rank23,
+        Rank::Rank24,
+        Rank::Rank25,
+    ];
+
+    const LABELS: [&'static str; 25] = [
+        "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15", "16",
+        "17", "18", "19", "20", "21", "22", "23", "24", "25",
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn from_index(idx: isize) -> Option<Self> {
+        usize::try_from(idx)
+            .ok()
+            .and_then(|idx| Self::ALL.get(idx).copied())
+    }
+
+    pub fn label(&self) -> &'static str {
+        Self::LABELS[self.index()]
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rank| rank.label() == label)
     }
 }
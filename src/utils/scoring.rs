@@ -0,0 +1,22 @@
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
+#[cfg_attr(feature = "tui", derive(clap::ValueEnum))]
+pub enum ScoringRule {
+    #[default]
+    Glinski,
+    FideStyle,
+}
+
+impl ScoringRule {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Glinski => "glinski",
+            Self::FideStyle => "fide-style",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        [Self::Glinski, Self::FideStyle]
+            .into_iter()
+            .find(|scoring| scoring.label().eq_ignore_ascii_case(label))
+    }
+}
@@ -0,0 +1,480 @@
+use std::ops::RangeInclusive;
+
+use crate::{pieces::PieceType, unit::cell::Cell};
+
+use super::{
+    castling::{Castling, CastlingRights, Wing},
+    depth::Depth,
+    direction::Direction,
+    file::File,
+    player::Player,
+    rank::Rank,
+};
+
+// Glinski's army as (piece, file offset from the centre file, cells in front
+// of the home edge). Smaller boards keep only the pieces that still fit
+// behind the pawns, so depth 5 loses the rooks and depth 4 the knights too.
+const GLINSKI_ARMY: [(PieceType, isize, isize); 9] = [
+    (PieceType::King, 1, 0),
+    (PieceType::Queen, -1, 0),
+    (PieceType::Rook, -3, 0),
+    (PieceType::Rook, 3, 0),
+    (PieceType::Bishop, 0, 0),
+    (PieceType::Bishop, 0, 1),
+    (PieceType::Bishop, 0, 2),
+    (PieceType::Knight, -2, 0),
+    (PieceType::Knight, 2, 0),
+];
+
+// McCooey's army in the same form. It is more compact than Glinski's: the
+// seven pawns form a chevron from c1 to i1 with its tip on f4, and every
+// piece fits inside it.
+const MCCOOEY_ARMY: [(PieceType, isize, isize); 9] = [
+    (PieceType::King, 1, 0),
+    (PieceType::Queen, -1, 0),
+    (PieceType::Rook, -2, 0),
+    (PieceType::Rook, 2, 0),
+    (PieceType::Bishop, 0, 0),
+    (PieceType::Bishop, 0, 1),
+    (PieceType::Bishop, 0, 2),
+    (PieceType::Knight, -1, 1),
+    (PieceType::Knight, 1, 1),
+];
+
+const MCCOOEY_PAWN_FILES: RangeInclusive<isize> = -3..=3;
+
+const SHAFRAN_WHITE_PIECES: [(PieceType, Cell); 9] = [
+    (PieceType::King, unsafe {
+        Cell::from_raw_parts(Rank::Rank2, File::FileD)
+    }),
+    (PieceType::Queen, unsafe {
+        Cell::from_raw_parts(Rank::Rank2, File::FileC)
+    }),
+    (PieceType::Rook, unsafe {
+        Cell::from_raw_parts(Rank::Rank2, File::FileA)
+    }),
+    (PieceType::Rook, unsafe {
+        Cell::from_raw_parts(Rank::Rank1, File::FileG)
+    }),
+    (PieceType::Bishop, unsafe {
+        Cell::from_raw_parts(Rank::Rank1, File::FileD)
+    }),
+    (PieceType::Bishop, unsafe {
+        Cell::from_raw_parts(Rank::Rank1, File::FileE)
+    }),
+    (PieceType::Bishop, unsafe {
+        Cell::from_raw_parts(Rank::Rank2, File::FileE)
+    }),
+    (PieceType::Knight, unsafe {
+        Cell::from_raw_parts(Rank::Rank2, File::FileB)
+    }),
+    (PieceType::Knight, unsafe {
+        Cell::from_raw_parts(Rank::Rank2, File::FileF)
+    }),
+];
+
+const SHAFRAN_BLACK_PIECES: [(PieceType, Cell); 9] = [
+    (PieceType::King, unsafe {
+        Cell::from_raw_parts(Rank::Rank8, File::FileH)
+    }),
+    (PieceType::Queen, unsafe {
+        Cell::from_raw_parts(Rank::Rank7, File::FileI)
+    }),
+    (PieceType::Rook, unsafe {
+        Cell::from_raw_parts(Rank::Rank5, File::FileL)
+    }),
+    (PieceType::Rook, unsafe {
+        Cell::from_raw_parts(Rank::Rank10, File::FileE)
+    }),
+    (PieceType::Bishop, unsafe {
+        Cell::from_raw_parts(Rank::Rank9, File::FileH)
+    }),
+    (PieceType::Bishop, unsafe {
+        Cell::from_raw_parts(Rank::Rank10, File::FileG)
+    }),
+    (PieceType::Bishop, unsafe {
+        Cell::from_raw_parts(Rank::Rank9, File::FileG)
+    }),
+    (PieceType::Knight, unsafe {
+        Cell::from_raw_parts(Rank::Rank6, File::FileK)
+    }),
+    (PieceType::Knight, unsafe {
+        Cell::from_raw_parts(Rank::Rank10, File::FileF)
+    }),
+];
+
+const SHAFRAN_WHITE_PAWN_STARTING_CELLS: [Cell; 9] = [
+    unsafe { Cell::from_raw_parts(Rank::Rank3, File::FileB) },
+    unsafe { Cell::from_raw_parts(Rank::Rank3, File::FileC) },
+    unsafe { Cell::from_raw_parts(Rank::Rank3, File::FileD) },
+    unsafe { Cell::from_raw_parts(Rank::Rank3, File::FileE) },
+    unsafe { Cell::from_raw_parts(Rank::Rank3, File::FileF) },
+    unsafe { Cell::from_raw_parts(Rank::Rank2, File::FileG) },
+    unsafe { Cell::from_raw_parts(Rank::Rank1, File::FileH) },
+    unsafe { Cell::from_raw_parts(Rank::Rank1, File::FileI) },
+    unsafe { Cell::from_raw_parts(Rank::Rank1, File::FileK) },
+];
+
+const SHAFRAN_BLACK_PAWN_STARTING_CELLS: [Cell; 9] = [
+    unsafe { Cell::from_raw_parts(Rank::Rank5, File::FileK) },
+    unsafe { Cell::from_raw_parts(Rank::Rank6, File::FileI) },
+    unsafe { Cell::from_raw_parts(Rank::Rank7, File::FileH) },
+    unsafe { Cell::from_raw_parts(Rank::Rank8, File::FileG) },
+    unsafe { Cell::from_raw_parts(Rank::Rank9, File::FileF) },
+    unsafe { Cell::from_raw_parts(Rank::Rank9, File::FileE) },
+    unsafe { Cell::from_raw_parts(Rank::Rank9, File::FileD) },
+    unsafe { Cell::from_raw_parts(Rank::Rank8, File::FileC) },
+    unsafe { Cell::from_raw_parts(Rank::Rank7, File::FileB) },
+];
+
+// The long diagonal between the two armies. Unmoved Shafran pawns may run
+// forward as far as this line, so how far depends on where they start.
+const SHAFRAN_CENTRE_LINE: [Cell; 11] = [
+    unsafe { Cell::from_raw_parts(Rank::Rank6, File::FileA) },
+    unsafe { Cell::from_raw_parts(Rank::Rank6, File::FileB) },
+    unsafe { Cell::from_raw_parts(Rank::Rank6, File::FileC) },
+    unsafe { Cell::from_raw_parts(Rank::Rank6, File::FileD) },
+    unsafe { Cell::from_raw_parts(Rank::Rank6, File::FileE) },
+    unsafe { Cell::from_raw_parts(Rank::Rank6, File::FileF) },
+    unsafe { Cell::from_raw_parts(Rank::Rank5, File::FileG) },
+    unsafe { Cell::from_raw_parts(Rank::Rank4, File::FileH) },
+    unsafe { Cell::from_raw_parts(Rank::Rank3, File::FileI) },
+    unsafe { Cell::from_raw_parts(Rank::Rank2, File::FileK) },
+    unsafe { Cell::from_raw_parts(Rank::Rank1, File::FileL) },
+];
+const SHAFRAN_WHITE_CASTLINGS: [Castling; 2] = [
+    Castling {
+        wing: Wing::King,
+        king: unsafe { Cell::from_raw_parts(Rank::Rank2, File::FileD) },
+        rook: unsafe { Cell::from_raw_parts(Rank::Rank1, File::FileG) },
+        direction: Direction::Clock4,
+    },
+    Castling {
+        wing: Wing::Queen,
+        king: unsafe { Cell::from_raw_parts(Rank::Rank2, File::FileD) },
+        rook: unsafe { Cell::from_raw_parts(Rank::Rank2, File::FileA) },
+        direction: Direction::Clock10,
+    },
+];
+
+const SHAFRAN_BLACK_CASTLINGS: [Castling; 2] = [
+    Castling {
+        wing: Wing::King,
+        king: unsafe { Cell::from_raw_parts(Rank::Rank8, File::FileH) },
+        rook: unsafe { Cell::from_raw_parts(Rank::Rank10, File::FileE) },
+        direction: Direction::Clock10,
+    },
+    Castling {
+        wing: Wing::Queen,
+        king: unsafe { Cell::from_raw_parts(Rank::Rank8, File::FileH) },
+        rook: unsafe { Cell::from_raw_parts(Rank::Rank5, File::FileL) },
+        direction: Direction::Clock4,
+    },
+];
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
+#[cfg_attr(feature = "tui", derive(clap::ValueEnum))]
+pub enum Variant {
+    #[default]
+    Glinski,
+    #[cfg_attr(feature = "tui", value(name = "mccooey"))]
+    McCooey,
+    Shafran,
+}
+
+impl Variant {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Glinski => "glinski",
+            Self::McCooey => "mccooey",
+            Self::Shafran => "shafran",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        [Self::Glinski, Self::McCooey, Self::Shafran]
+            .into_iter()
+            .find(|variant| variant.label().eq_ignore_ascii_case(label))
+    }
+
+    /// Whether a game of this variant can be set up on a board of `depth`.
+    /// Glinski's army shrinks with the board but needs at least four rings
+    /// to keep both kings; the other variants only have a full-size setup.
+    pub fn playable_on(self, depth: Depth) -> bool {
+        match self {
+            Self::Glinski => depth.radius() >= 3,
+            Self::McCooey | Self::Shafran => depth == Depth::GLINSKI,
+        }
+    }
+
+    pub(crate) fn setup(self, color: Player, depth: Depth) -> Vec<(PieceType, Cell)> {
+        let pieces = match (self, color) {
+            (Self::Glinski, _) => GLINSKI_ARMY
+                .into_iter()
+                .filter(|(_, offset, rank)| *rank < glinski_pawn_rank(depth, *offset))
+                .map(|(piece_type, offset, rank)| {
+                    (piece_type, home_cell(color, depth, offset, rank))
+                })
+                .collect(),
+            (Self::McCooey, _) => MCCOOEY_ARMY
+                .into_iter()
+                .map(|(piece_type, offset, rank)| {
+                    (piece_type, home_cell(color, depth, offset, rank))
+                })
+                .collect(),
+            (Self::Shafran, Player::White) => SHAFRAN_WHITE_PIECES.to_vec(),
+            (Self::Shafran, Player::Black) => SHAFRAN_BLACK_PIECES.to_vec(),
+        };
+
+        let pawns = self
+            .pawn_starting_cells(color, depth)
+            .into_iter()
+            .map(|cell| (PieceType::Pawn, cell));
+
+        pieces.into_iter().chain(pawns).collect()
+    }
+
+    pub(crate) fn pawn_starting_cells(self, color: Player, depth: Depth) -> Vec<Cell> {
+        match (self, color) {
+            (Self::Glinski, _) => {
+                let radius = depth.radius();
+                (1 - radius..radius)
+                    .map(|offset| home_cell(color, depth, offset, glinski_pawn_rank(depth, offset)))
+                    .collect()
+            }
+            (Self::McCooey, _) => MCCOOEY_PAWN_FILES
+                .map(|offset| home_cell(color, depth, offset, 3 - offset.abs()))
+                .collect(),
+            (Self::Shafran, Player::White) => SHAFRAN_WHITE_PAWN_STARTING_CELLS.to_vec(),
+            (Self::Shafran, Player::Black) => SHAFRAN_BLACK_PAWN_STARTING_CELLS.to_vec(),
+        }
+    }
+
+    /// How many cells a pawn on `cell` may advance in one move.
+    pub(crate) fn pawn_steps(self, color: Player, cell: Cell, forward: Direction) -> usize {
+        if !self.pawn_starting_cells(color, cell.depth).contains(&cell) {
+            return 1;
+        }
+
+        match self {
+            Self::Glinski => 2,
+            Self::McCooey if cell.file == File::FileF => 1,
+            Self::McCooey => 2,
+            Self::Shafran => std::iter::successors(cell.next(forward), |cell| cell.next(forward))
+                .position(|cell| SHAFRAN_CENTRE_LINE.contains(&cell))
+                .map_or(1, |idx| idx + 1),
+        }
+    }
+
+    pub(crate) fn castlings(self, color: Player) -> &'static [Castling] {
+        match (self, color) {
+            (Self::Glinski | Self::McCooey, _) => &[],
+            (Self::Shafran, Player::White) => &SHAFRAN_WHITE_CASTLINGS,
+            (Self::Shafran, Player::Black) => &SHAFRAN_BLACK_CASTLINGS,
+        }
+    }
+
+    pub(crate) fn castling_rights(self) -> CastlingRights {
+        let mut rights = CastlingRights::default();
+        for color in [Player::White, Player::Black] {
+            for castling in self.castlings(color) {
+                rights.grant(color, castling.wing);
+            }
+        }
+
+        rights
+    }
+
+    /// The far edge of every file. McCooey's and Shafran's games promote on
+    /// the same cells as Glinski's; they only move the pieces and pawns.
+    pub(crate) fn promotion_cells(self, color: Player, depth: Depth) -> Vec<Cell> {
+        depth
+            .file_range()
+            .map(|file| {
+                let ranks = depth.rank_range(file);
+                let rank = match color {
+                    Player::White => ranks.hi(),
+                    Player::Black => ranks.lo(),
+                };
+
+                Cell::new_in(depth, rank, file)
+            })
+            .collect()
+    }
+}
+
+/// How many cells in front of the home edge a Glinski pawn starts on the
+/// file `offset` files from the centre. The pawns form a chevron whose tip is
+/// just behind the centre cell.
+fn glinski_pawn_rank(depth: Depth, offset: isize) -> isize {
+    depth.radius() - 1 - offset.abs()
+}
+
+/// The cell `rank` cells in front of `color`'s home edge on the file `offset`
+/// files from the centre. Black's cells mirror White's across the centre rank.
+fn home_cell(color: Player, depth: Depth, offset: isize, rank: isize) -> Cell {
+    let radius = depth.radius();
+    let rank = match color {
+        Player::White => rank,
+        Player::Black => 2 * radius - offset.abs() - rank,
+    };
+
+    Cell::new_in(
+        depth,
+        Rank::from_index(rank).unwrap(),
+        File::from_index(radius + offset).unwrap(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        board::Board,
+        unit::cell::Cell,
+        utils::{depth::Depth, file::File, player::Player, rank::Rank},
+    };
+
+    use super::Variant;
+
+    fn destinations(board: &mut Board, cell: Cell) -> Vec<Cell> {
+        board
+            .legal_moves(cell)
+            .into_iter()
+            .map(|mov| mov.move_to)
+            .collect()
+    }
+
+    #[test]
+    fn mccooey_starts_with_seven_pawns_a_side() {
+        let board = Board::with_variant(Variant::McCooey);
+
+        for color in [Player::White, Player::Black] {
+            assert_eq!(board.occupied_cells(color).len(), 16);
+        }
+        assert_eq!(
+            board.to_fen(Player::White),
+            "6/7/P6p/RP5pr/QNP4pnq/BBBP3pbbb/KNP4pnk/RP5pr/P6p/7/6 w - 0 1"
+        );
+    }
+
+    #[test]
+    fn mccooey_centre_pawns_cannot_double_step() {
+        let mut board = Board::with_variant(Variant::McCooey);
+
+        assert_eq!(
+            destinations(&mut board, Cell::new(Rank::Rank4, File::FileF)),
+            [Cell::new(Rank::Rank5, File::FileF)]
+        );
+        assert_eq!(
+            destinations(&mut board, Cell::new(Rank::Rank3, File::FileE)).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn mccooey_pawns_promote_on_the_far_edge_like_glinski() {
+        for color in [Player::White, Player::Black] {
+            assert_eq!(
+                Variant::McCooey.promotion_cells(color, Depth::GLINSKI),
+                Variant::Glinski.promotion_cells(color, Depth::GLINSKI)
+            );
+        }
+
+        let (mut board, _) = Board::from_fen("6/5P1/8/9/10/5K3P1/10/9/8/7/5k w - 0 1").unwrap();
+        board.set_variant(Variant::McCooey);
+        for (pawn, edge) in [
+            (
+                Cell::new(Rank::Rank6, File::FileB),
+                Cell::new(Rank::Rank7, File::FileB),
+            ),
+            (
+                Cell::new(Rank::Rank10, File::FileF),
+                Cell::new(Rank::Rank11, File::FileF),
+            ),
+        ] {
+            let moves = board.legal_moves(pawn);
+            assert_eq!(moves.len(), 4);
+            assert!(
+                moves
+                    .iter()
+                    .all(|mov| mov.move_to == edge && mov.move_type.is_promotion())
+            );
+        }
+    }
+
+    #[test]
+    fn shafran_pawns_may_run_to_the_centre_line() {
+        let mut board = Board::with_variant(Variant::Shafran);
+
+        assert_eq!(
+            board.to_fen(Player::White),
+            "1R4/1NP3p/1QP4p/BKP5p/BBP5pr/1NP5pn1/RP5pbb/P5pkb/P4pq1/P3pn1/4r1 w KQkq - 0 1"
+        );
+
+        let steps = [
+            (Cell::new(Rank::Rank3, File::FileB), 3),
+            (Cell::new(Rank::Rank1, File::FileH), 3),
+            (Cell::new(Rank::Rank1, File::FileI), 2),
+            (Cell::new(Rank::Rank1, File::FileK), 1),
+            (Cell::new(Rank::Rank7, File::FileB), 1),
+            (Cell::new(Rank::Rank9, File::FileF), 3),
+        ];
+        for (cell, expected) in steps {
+            assert_eq!(destinations(&mut board, cell).len(), expected, "{cell:?}");
+        }
+    }
+
+    #[test]
+    fn smaller_boards_get_a_reduced_glinski_army() {
+        let depth = Depth::new(5).unwrap();
+        let mut board = Board::with_depth(depth, Variant::Glinski);
+
+        assert_eq!(
+            board.to_fen(Player::White),
+            "5/P4p/NP3pn/Q1P2p1q/BBBP1pbbb/K1P2p1k/NP3pn/P4p/5 w - 0 1"
+        );
+        assert_eq!(
+            destinations(&mut board, Cell::new_in(depth, Rank::Rank3, File::FileD)),
+            [
+                Cell::new_in(depth, Rank::Rank4, File::FileD),
+                Cell::new_in(depth, Rank::Rank5, File::FileD)
+            ]
+        );
+
+        let promotion = Variant::Glinski.promotion_cells(Player::White, depth);
+        assert_eq!(promotion.len(), 9);
+        assert!(promotion.contains(&Cell::new_in(depth, Rank::Rank9, File::FileE)));
+        assert!(promotion.contains(&Cell::new_in(depth, Rank::Rank5, File::FileI)));
+
+        assert_eq!(board.perft(Player::White, 1), 37);
+        assert_eq!(board.perft(Player::White, 2), 1328);
+    }
+
+    #[test]
+    fn only_glinski_shrinks_and_only_down_to_four_rings() {
+        let small = Depth::new(4).unwrap();
+        let board = Board::with_depth(small, Variant::Glinski);
+        assert_eq!(
+            board.to_fen(Player::White),
+            "4/P3p/QP2pq/BBP1pbb/KP2pk/P3p/4 w - 0 1"
+        );
+
+        assert!(!Variant::Glinski.playable_on(Depth::new(3).unwrap()));
+        assert!(!Variant::McCooey.playable_on(small));
+        assert!(!Variant::Shafran.playable_on(small));
+        assert!(Variant::Shafran.playable_on(Depth::default()));
+    }
+
+    #[test]
+    fn perft_from_the_start_position() {
+        let mut glinski = Board::new();
+        let mut mccooey = Board::with_variant(Variant::McCooey);
+
+        let mut shafran = Board::with_variant(Variant::Shafran);
+
+        assert_eq!(glinski.perft(Player::White, 1), 51);
+        assert_eq!(mccooey.perft(Player::White, 1), 31);
+        assert_eq!(mccooey.perft(Player::White, 2), 947);
+        assert_eq!(shafran.perft(Player::White, 1), 54);
+        assert_eq!(shafran.perft(Player::White, 2), 2817);
+    }
+}